@@ -1,19 +1,47 @@
+use std::iter::FusedIterator;
+
 use crate::{avl_node::AvlNode, avl_tree::AvlTree};
 
 /// AVL树不可变引用迭代器
+///
+/// 正向栈中每项保留节点的右子树，反向栈中每项保留节点的左子树，两个栈中的数据互不相交，
+/// 一端的栈为空时从另一端的栈底一项拆出剩余数据
 pub struct Iter<'a, T> {
-    pub(crate) stack: Vec<&'a AvlNode<T>>,
+    pub(crate) stack: Vec<(&'a T, Option<&'a AvlNode<T>>)>,
+    pub(crate) back: Vec<(&'a T, Option<&'a AvlNode<T>>)>,
+    /// 剩余数据个数
+    pub(crate) len: usize,
 }
 
-impl<T> Iter<'_, T> {
+impl<'a, T> Iter<'a, T> {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// 将节点及其左链压入正向栈
+    pub(crate) fn push_left(&mut self, mut node: Option<&'a AvlNode<T>>) {
+        while let Some(child) = node {
+            self.stack.push((&child.data, child.rch.0.as_deref()));
+            node = child.lch.0.as_deref();
+        }
+    }
+
+    /// 将节点及其右链压入反向栈
+    fn push_right(&mut self, mut node: Option<&'a AvlNode<T>>) {
+        while let Some(child) = node {
+            self.back.push((&child.data, child.lch.0.as_deref()));
+            node = child.rch.0.as_deref();
+        }
+    }
 }
 
 impl<T> Default for Iter<'_, T> {
     fn default() -> Self {
-        Self { stack: Vec::new() }
+        Self {
+            stack: Vec::new(),
+            back: Vec::new(),
+            len: 0,
+        }
     }
 }
 
@@ -21,25 +49,107 @@ impl<'a, T> Iterator for Iter<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let node = self.stack.pop()?;
+        if self.stack.is_empty() {
+            // 剩余的最小值在反向栈底一项的左子树中，左子树为空时即该项本身
+            match self.back.first_mut()?.1.take() {
+                Some(left) => self.push_left(Some(left)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.back.remove(0).0);
+                }
+            }
+        }
+        let (data, right) = self.stack.pop()?;
         // 处理右子树
-        let mut curr = node.rch.0.as_deref();
-        while let Some(child) = curr {
-            self.stack.push(child);
-            curr = child.lch.0.as_deref();
+        self.push_left(right);
+        self.len -= 1;
+        Some(data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Iter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            // 剩余的最大值在正向栈底一项的右子树中，右子树为空时即该项本身
+            match self.stack.first_mut()?.1.take() {
+                Some(right) => self.push_right(Some(right)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.stack.remove(0).0);
+                }
+            }
+        }
+        let (data, left) = self.back.pop()?;
+        // 处理左子树
+        self.push_right(left);
+        self.len -= 1;
+        Some(data)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// AVL树区间迭代器
+///
+/// 正向迭代器从下界开始，反向迭代器从上界开始，两者都可能越过区间的另一端，
+/// 由区间内的剩余数据个数保证两端取出的数据不重叠
+pub struct Range<'a, T> {
+    pub(crate) front: Iter<'a, T>,
+    pub(crate) back: Iter<'a, T>,
+    /// 区间内剩余数据个数
+    pub(crate) len: usize,
+}
+
+impl<'a, T> Iterator for Range<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.front.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for Range<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
         }
-        Some(&node.data)
+        self.len -= 1;
+        self.back.next_back()
     }
 }
 
-/// AVL树所有权转移迭代器
+impl<T> ExactSizeIterator for Range<'_, T> {}
+
+impl<T> FusedIterator for Range<'_, T> {}
+
+/// AVL树所有权转移迭代器，两个栈的划分方式与`Iter`相同
 pub struct IntoIter<T> {
     stack: Vec<AvlNode<T>>,
+    back: Vec<AvlNode<T>>,
+    len: usize,
 }
 
 impl<T> IntoIter<T> {
     pub fn new(tree: AvlTree<T>) -> Self {
-        let mut iter = Self { stack: vec![] };
+        let mut iter = Self {
+            stack: vec![],
+            back: vec![],
+            len: tree.len(),
+        };
         iter.push_left(tree.0);
         iter
     }
@@ -51,20 +161,66 @@ impl<T> IntoIter<T> {
             self.stack.push(*boxed_node);
         }
     }
+
+    fn push_right(&mut self, mut node: Option<Box<AvlNode<T>>>) {
+        while let Some(mut boxed_node) = node {
+            let right = boxed_node.rch.0.take();
+            node = right;
+            self.back.push(*boxed_node);
+        }
+    }
 }
 
 impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.stack.is_empty() {
+            match self.back.first_mut()?.lch.0.take() {
+                Some(left) => self.push_left(Some(left)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.back.remove(0).data);
+                }
+            }
+        }
         let mut node = self.stack.pop()?;
         self.push_left(node.rch.0.take());
+        self.len -= 1;
+        Some(node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            match self.stack.first_mut()?.rch.0.take() {
+                Some(right) => self.push_right(Some(right)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.stack.remove(0).data);
+                }
+            }
+        }
+        let mut node = self.back.pop()?;
+        self.push_right(node.lch.0.take());
+        self.len -= 1;
         Some(node.data)
     }
 }
 
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
 #[cfg(test)]
 mod tests {
+    use std::ops::Bound;
+
     use super::*;
 
     #[test]
@@ -104,4 +260,157 @@ mod tests {
         // Then
         assert_eq!(vec![1, 2, 3, 4, 5, 6, 7], nums);
     }
+
+    #[test]
+    fn test_range() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in (0..20).map(|i| i * 5) {
+            t.insert(i);
+        }
+
+        // When
+        let inner: Vec<_> = t.range(12..=40).copied().collect();
+        let exclusive: Vec<_> = t.range(15..40).copied().collect();
+        let head: Vec<_> = t.range(..=10).copied().collect();
+        let tail: Vec<_> = t.range(83..).copied().collect();
+        let all = t.range(..).count();
+        let empty = t.range(41..44).count();
+        let reversed = t.range((Bound::Included(50), Bound::Excluded(20))).count();
+
+        // Then
+        assert_eq!(vec![15, 20, 25, 30, 35, 40], inner);
+        assert_eq!(vec![15, 20, 25, 30, 35], exclusive);
+        assert_eq!(vec![0, 5, 10], head);
+        assert_eq!(vec![85, 90, 95], tail);
+        assert_eq!(20, all);
+        assert_eq!(0, empty);
+        assert_eq!(0, reversed);
+    }
+
+    #[test]
+    fn test_range_double_ended() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in 0..30 {
+            t.insert(i * 2);
+        }
+
+        for (lo, hi) in [(0, 58), (3, 41), (10, 10), (11, 11), (-5, 100), (40, 20)] {
+            let expected: Vec<_> = (lo..=hi)
+                .filter(|i| (0..60).contains(i) && i % 2 == 0)
+                .collect();
+            for mask in [0, u32::MAX, 0b10, 0b1101, 0xdead_beef] {
+                // When
+                let drained = drain_both_ends(t.range(lo..=hi).copied(), mask);
+
+                // Then
+                assert_eq!(expected, drained);
+            }
+            assert_eq!(expected.len(), t.range(lo..=hi).len());
+        }
+        let mut range = t.range(5..12);
+        assert_eq!(3, range.len());
+        assert_eq!((Some(&10), Some(&6)), (range.next_back(), range.next()));
+        assert_eq!(1, range.len());
+        assert_eq!(Some(&8), range.next_back());
+        assert_eq!(None, range.next());
+        assert_eq!(None, range.next_back());
+    }
+
+    /// 按mask的各位交替从两端取数据，1取后端，0取前端
+    fn drain_both_ends<I>(mut iter: I, mut mask: u32) -> Vec<i32>
+    where
+        I: DoubleEndedIterator<Item = i32>,
+    {
+        let (mut front, mut back) = (Vec::new(), Vec::new());
+        loop {
+            let next = if mask & 1 == 1 {
+                iter.next_back().map(|v| back.push(v))
+            } else {
+                iter.next().map(|v| front.push(v))
+            };
+            if next.is_none() {
+                break;
+            }
+            mask = mask.rotate_right(1);
+        }
+        front.extend(back.into_iter().rev());
+        front
+    }
+
+    #[test]
+    fn test_double_ended() {
+        for n in 0..20 {
+            // Given
+            let mut t = AvlTree::new();
+            for i in 0..n {
+                t.insert(i);
+            }
+
+            for mask in [0, u32::MAX, 0b10, 0b1101, 0xdead_beef] {
+                // When
+                let borrowed = drain_both_ends(t.iter().copied(), mask);
+
+                // Then
+                assert_eq!((0..n).collect::<Vec<_>>(), borrowed);
+            }
+            let reversed: Vec<_> = t.iter().rev().copied().collect();
+            assert_eq!((0..n).rev().collect::<Vec<_>>(), reversed);
+            assert_eq!(
+                (0..n).collect::<Vec<_>>(),
+                drain_both_ends(t.into_iter(), 0x2545_f491)
+            );
+        }
+    }
+
+    #[test]
+    fn test_fused() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [4, 2, 6] {
+            t.insert(i);
+        }
+        let mut iter = t.iter();
+
+        // When
+        let front = iter.next();
+        let back = iter.next_back();
+        let middle = iter.next_back();
+
+        // Then
+        assert_eq!((Some(&2), Some(&6), Some(&4)), (front, back, middle));
+        assert_eq!(None, iter.next());
+        assert_eq!(None, iter.next_back());
+        assert_eq!(None, iter.next());
+        assert_eq!(
+            vec![6, 4],
+            t.lower_bound(&3).rev().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_exact_size() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in 0..10 {
+            t.insert(i);
+        }
+        let mut iter = t.iter();
+
+        // When
+        iter.next();
+        iter.next_back();
+        iter.next_back();
+
+        // Then
+        assert_eq!(7, iter.len());
+        assert_eq!(7, iter.count());
+        assert_eq!(6, t.lower_bound(&4).len());
+        assert_eq!(5, t.upper_bound(&4).len());
+        assert_eq!(0, t.upper_bound(&9).len());
+        let mut into_iter = t.into_iter();
+        into_iter.next_back();
+        assert_eq!(9, into_iter.len());
+    }
 }
@@ -0,0 +1,673 @@
+//! ## Brief
+//! 基于AVL树的有序映射，键值对存放在树节点中，只按键比较，提供entry API与键、值迭代器
+//!
+//! ## Version
+//! 1.0
+
+use std::{borrow::Borrow, cmp::Ordering, iter::FusedIterator};
+
+use crate::{
+    avl_iter,
+    avl_node::{AvlNode, RotMod},
+    avl_tree::AvlTree,
+};
+
+/// 基于AVL树的有序映射，节点数据为键值对，只按键比较
+///
+/// ## Example
+/// ```rust
+/// use avl_tree::avl_map::AvlMap;
+///
+/// let mut m = AvlMap::new();
+/// assert_eq!(None, m.insert("b", 1));
+/// assert_eq!(Some(1), m.insert("b", 2));
+/// *m.entry("a").or_insert(0) += 10;
+/// m.entry("b").and_modify(|v| *v *= 3).or_insert(0);
+/// assert_eq!(vec![&"a", &"b"], m.keys().collect::<Vec<_>>());
+/// assert_eq!(vec![&10, &6], m.values().collect::<Vec<_>>());
+/// assert_eq!(Some(6), m.remove("b"));
+/// ```
+#[derive(Debug)]
+pub struct AvlMap<K, V> {
+    tree: AvlTree<(K, V)>,
+}
+
+/// 从子树根到某节点的路径，栈顶为第一步，Less表示走左孩子，Greater表示走右孩子
+type Path = Vec<Ordering>;
+
+/// 子树根旋转后修正路径，up为升为新根的孩子一侧
+fn rot_path(path: &mut Path, up: Ordering) {
+    let down = up.reverse();
+    match path.pop() {
+        // 原根降为新根的孩子
+        None => path.push(down),
+        Some(d) if d == down => {
+            path.push(down);
+            path.push(down);
+        }
+        Some(_) => match path.pop() {
+            // 升起的孩子成为新根
+            None => {}
+            Some(d) if d == up => path.push(up),
+            // 升起孩子的内侧子树挂到原根下
+            Some(_) => {
+                path.push(up);
+                path.push(down);
+            }
+        },
+    }
+}
+
+/// 按旋转模式修正路径
+fn rotate_path(path: &mut Path, rot_mod: &RotMod) {
+    let child_rot = |path: &mut Path, side: Ordering| {
+        if path.last() == Some(&side) {
+            path.pop();
+            rot_path(path, side.reverse());
+            path.push(side);
+        }
+    };
+    match rot_mod {
+        RotMod::LeftRot => rot_path(path, Ordering::Greater),
+        RotMod::RightRot => rot_path(path, Ordering::Less),
+        RotMod::LeftRightRot => {
+            child_rot(path, Ordering::Less);
+            rot_path(path, Ordering::Less);
+        }
+        RotMod::RightLeftRot => {
+            child_rot(path, Ordering::Greater);
+            rot_path(path, Ordering::Greater);
+        }
+        RotMod::NotRot => {}
+    }
+}
+
+/// 沿路径找到节点的键值对
+fn pair_at<'a, K, V>(mut tree: &'a mut AvlTree<(K, V)>, path: &Path) -> &'a mut (K, V) {
+    for dir in path.iter().rev() {
+        let rt = tree.0.as_mut().expect("path leads to an existing node");
+        tree = match dir {
+            Ordering::Less => &mut rt.lch,
+            _ => &mut rt.rch,
+        };
+    }
+    &mut tree
+        .0
+        .as_mut()
+        .expect("path leads to an existing node")
+        .data
+}
+
+/// 查找键，返回到该节点或到其插入位置的路径
+fn search<K, V, Q>(tree: &AvlTree<(K, V)>, key: &Q) -> (Path, bool)
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let mut path = Vec::new();
+    let mut cur = tree.0.as_ref();
+    while let Some(rt) = cur {
+        let dir = key.cmp(rt.data.0.borrow());
+        cur = match dir {
+            Ordering::Less => rt.lch.0.as_ref(),
+            Ordering::Greater => rt.rch.0.as_ref(),
+            Ordering::Equal => break,
+        };
+        path.push(dir);
+    }
+    path.reverse();
+    (path, cur.is_some())
+}
+
+/// 插入不存在的键并沿途旋转，返回到新节点的路径
+fn insert_new<K: Ord, V>(tree: &mut AvlTree<(K, V)>, key: K, value: V) -> Path {
+    let Some(rt) = &mut tree.0 else {
+        tree.0 = Some(Box::new(AvlNode::new(
+            (key, value),
+            AvlTree::default(),
+            AvlTree::default(),
+        )));
+        tree.update();
+        return Vec::new();
+    };
+    let dir = key.cmp(&rt.data.0);
+    let mut path = match dir {
+        Ordering::Less => insert_new(&mut rt.lch, key, value),
+        _ => insert_new(&mut rt.rch, key, value),
+    };
+    path.push(dir);
+    let rot_mod = rt.rot_mod();
+    rotate_path(&mut path, &rot_mod);
+    tree.rotate(rot_mod);
+    path
+}
+
+/// 删除键，沿途旋转，返回被删除的键值对
+fn remove_entry<K, V, Q>(tree: &mut AvlTree<(K, V)>, key: &Q) -> Option<(K, V)>
+where
+    K: Borrow<Q>,
+    Q: Ord + ?Sized,
+{
+    let rt = tree.0.as_mut()?;
+    let removed = match key.cmp(rt.data.0.borrow()) {
+        Ordering::Less => remove_entry(&mut rt.lch, key),
+        Ordering::Greater => remove_entry(&mut rt.rch, key),
+        Ordering::Equal => return tree.take_root(),
+    };
+    tree.rebalance();
+    removed
+}
+
+impl<K, V> AvlMap<K, V> {
+    /// 创建一个空映射
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 获取树的高度
+    pub fn height(&self) -> isize {
+        self.tree.height()
+    }
+
+    /// 获取键值对个数
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    /// 判断是否为空
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// 清空
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+
+    /// 按键的顺序迭代键值对
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter(self.tree.iter())
+    }
+
+    /// 按顺序迭代键
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys(self.tree.iter())
+    }
+
+    /// 按键的顺序迭代值
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values(self.tree.iter())
+    }
+
+    /// 按键的顺序迭代值的可变引用，键参与排序，因此不提供键的可变引用
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        let mut iter = ValuesMut {
+            stack: Vec::new(),
+            back: Vec::new(),
+            len: self.tree.len(),
+        };
+        iter.push_left(self.tree.0.as_deref_mut());
+        iter
+    }
+}
+
+impl<K: Ord, V> AvlMap<K, V> {
+    /// 插入，键已存在时替换并返回旧值
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut e) => Some(e.insert(value)),
+            Entry::Vacant(e) => {
+                e.insert(value);
+                None
+            }
+        }
+    }
+
+    /// 获取键对应的值
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut cur = self.tree.0.as_ref();
+        while let Some(rt) = cur {
+            cur = match key.cmp(rt.data.0.borrow()) {
+                Ordering::Less => rt.lch.0.as_ref(),
+                Ordering::Greater => rt.rch.0.as_ref(),
+                Ordering::Equal => return Some(&rt.data.1),
+            };
+        }
+        None
+    }
+
+    /// 获取键对应的值的可变引用
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut cur = self.tree.0.as_deref_mut();
+        while let Some(rt) = cur {
+            cur = match key.cmp(rt.data.0.borrow()) {
+                Ordering::Less => rt.lch.0.as_deref_mut(),
+                Ordering::Greater => rt.rch.0.as_deref_mut(),
+                Ordering::Equal => return Some(&mut rt.data.1),
+            };
+        }
+        None
+    }
+
+    /// 判断键是否存在
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// 删除键，返回其对应的值
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        remove_entry(&mut self.tree, key).map(|(_, v)| v)
+    }
+
+    /// 获取键对应的条目，用于原地查询或修改
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match search(&self.tree, &key) {
+            (path, true) => Entry::Occupied(OccupiedEntry {
+                pair: pair_at(&mut self.tree, &path),
+            }),
+            (_, false) => Entry::Vacant(VacantEntry {
+                key,
+                tree: &mut self.tree,
+            }),
+        }
+    }
+}
+
+/// 默认构造
+impl<K, V> Default for AvlMap<K, V> {
+    fn default() -> Self {
+        Self {
+            tree: AvlTree::default(),
+        }
+    }
+}
+
+/// 映射中的条目
+pub enum Entry<'a, K, V> {
+    /// 键已存在
+    Occupied(OccupiedEntry<'a, K, V>),
+    /// 键不存在
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// 已存在的条目
+pub struct OccupiedEntry<'a, K, V> {
+    pair: &'a mut (K, V),
+}
+
+/// 不存在的条目
+pub struct VacantEntry<'a, K, V> {
+    key: K,
+    tree: &'a mut AvlTree<(K, V)>,
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(e) => e.key(),
+            Entry::Vacant(e) => e.key(),
+        }
+    }
+
+    /// 键不存在时插入default，返回值的可变引用
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// 键不存在时插入f的结果，返回值的可变引用
+    pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(f()),
+        }
+    }
+
+    /// 键已存在时修改其值
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut e) => {
+                f(e.get_mut());
+                Entry::Occupied(e)
+            }
+            Entry::Vacant(e) => Entry::Vacant(e),
+        }
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.pair.0
+    }
+
+    /// 条目的值
+    pub fn get(&self) -> &V {
+        &self.pair.1
+    }
+
+    /// 条目的值的可变引用
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.pair.1
+    }
+
+    /// 转换为与映射生命周期相同的可变引用
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.pair.1
+    }
+
+    /// 替换值，返回旧值
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(&mut self.pair.1, value)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// 条目的键
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// 插入值，返回其可变引用
+    pub fn insert(self, value: V) -> &'a mut V {
+        let path = insert_new(self.tree, self.key, value);
+        &mut pair_at(self.tree, &path).1
+    }
+}
+
+/// 键值对迭代器
+pub struct Iter<'a, K, V>(avl_iter::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Iter<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+/// 键迭代器
+pub struct Keys<'a, K, V>(avl_iter::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Keys<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, _)| k)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<K, V> FusedIterator for Keys<'_, K, V> {}
+
+/// 值迭代器
+pub struct Values<'a, K, V>(avl_iter::Iter<'a, (K, V)>);
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for Values<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(_, v)| v)
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<K, V> FusedIterator for Values<'_, K, V> {}
+
+/// 栈中待访问节点的值与仍归该栈所有的子树
+type ValueEntry<'a, K, V> = (&'a mut V, Option<&'a mut AvlNode<(K, V)>>);
+
+/// 值可变引用迭代器，两个栈的划分方式与`Iter`相同
+pub struct ValuesMut<'a, K, V> {
+    /// 每项保留节点的右子树
+    stack: Vec<ValueEntry<'a, K, V>>,
+    /// 每项保留节点的左子树
+    back: Vec<ValueEntry<'a, K, V>>,
+    /// 剩余值的个数
+    len: usize,
+}
+
+impl<'a, K, V> ValuesMut<'a, K, V> {
+    fn push_left(&mut self, mut node: Option<&'a mut AvlNode<(K, V)>>) {
+        while let Some(rt) = node {
+            // 将节点拆分为互不重叠的借用
+            let AvlNode { data, lch, rch, .. } = rt;
+            self.stack.push((&mut data.1, rch.0.as_deref_mut()));
+            node = lch.0.as_deref_mut();
+        }
+    }
+
+    fn push_right(&mut self, mut node: Option<&'a mut AvlNode<(K, V)>>) {
+        while let Some(rt) = node {
+            let AvlNode { data, lch, rch, .. } = rt;
+            self.back.push((&mut data.1, lch.0.as_deref_mut()));
+            node = rch.0.as_deref_mut();
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.stack.is_empty() {
+            match self.back.first_mut()?.1.take() {
+                Some(left) => self.push_left(Some(left)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.back.remove(0).0);
+                }
+            }
+        }
+        let (value, right) = self.stack.pop()?;
+        self.push_left(right);
+        self.len -= 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for ValuesMut<'_, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.back.is_empty() {
+            match self.stack.first_mut()?.1.take() {
+                Some(right) => self.push_right(Some(right)),
+                None => {
+                    self.len -= 1;
+                    return Some(self.stack.remove(0).0);
+                }
+            }
+        }
+        let (value, left) = self.back.pop()?;
+        self.push_right(left);
+        self.len -= 1;
+        Some(value)
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::test_util::xorshift;
+
+    use super::*;
+
+    #[test]
+    fn test_insert_get_remove() {
+        // Given
+        let mut m = AvlMap::new();
+
+        // When
+        let first = m.insert(3, "c");
+        let replaced = m.insert(3, "C");
+        m.insert(1, "a");
+        m.insert(2, "b");
+        if let Some(v) = m.get_mut(&1) {
+            *v = "A";
+        }
+
+        // Then
+        assert_eq!(None, first);
+        assert_eq!(Some("c"), replaced);
+        assert_eq!(Some(&"A"), m.get(&1));
+        assert_eq!(Some(&"C"), m.get(&3));
+        assert_eq!(None, m.get(&4));
+        assert_eq!(Some("b"), m.remove(&2));
+        assert_eq!(None, m.remove(&2));
+        assert!(!m.contains_key(&2));
+        assert_eq!(vec![(&1, &"A"), (&3, &"C")], m.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_entry() {
+        // Given
+        let mut counts = AvlMap::new();
+        let words = "b a c a b a d e f g h a".split(' ');
+
+        // When
+        for w in words {
+            counts
+                .entry(w.to_string())
+                .and_modify(|c| *c += 1)
+                .or_insert(1);
+        }
+        let vacant = counts.entry("z".to_string()).or_insert_with(|| 26);
+        *vacant += 1;
+
+        // Then
+        assert_eq!(Some(&4), counts.get("a"));
+        assert_eq!(Some(&2), counts.get("b"));
+        assert_eq!(Some(&27), counts.get("z"));
+        assert_eq!("h", counts.entry("h".to_string()).key());
+        assert!(counts.tree.check_invariants());
+    }
+
+    #[test]
+    fn test_iterators() {
+        // Given
+        let mut m = AvlMap::new();
+        for k in [5, 2, 8, 1, 9, 3] {
+            m.insert(k, k * 10);
+        }
+
+        // When
+        for v in m.values_mut() {
+            *v += 1;
+        }
+        let mut values = m.values_mut();
+        if let (Some(first), Some(last)) = (values.next(), values.next_back()) {
+            *first = 0;
+            *last = 100;
+        }
+
+        // Then
+        assert_eq!(
+            vec![1, 2, 3, 5, 8, 9],
+            m.keys().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![9, 8, 5, 3, 2, 1],
+            m.keys().rev().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![0, 21, 31, 51, 81, 100],
+            m.values().copied().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(&100), m.values().next_back());
+        assert!(!m.is_empty());
+        assert_eq!(6, m.len());
+        assert_eq!(6, m.keys().len());
+        assert_eq!(5, m.values().skip(1).len());
+        let mut values = m.values_mut();
+        values.next_back();
+        assert_eq!(5, values.len());
+    }
+
+    #[test]
+    fn test_random_against_btree_map() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        // Given
+        let mut m = AvlMap::new();
+        let mut expected = BTreeMap::new();
+
+        // When
+        for i in 0..2000 {
+            let k = xorshift(&mut state) % 300;
+            match xorshift(&mut state) % 3 {
+                0 => assert_eq!(expected.remove(&k), m.remove(&k)),
+                1 => assert_eq!(expected.insert(k, i), m.insert(k, i)),
+                _ => {
+                    *expected.entry(k).or_insert(0) += i;
+                    *m.entry(k).or_insert(0) += i;
+                }
+            }
+            assert!(m.tree.check_invariants());
+        }
+
+        // Then
+        assert_eq!(
+            expected.iter().collect::<Vec<_>>(),
+            m.iter().collect::<Vec<_>>()
+        );
+    }
+}
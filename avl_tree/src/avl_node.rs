@@ -9,6 +9,8 @@ pub struct AvlNode<T> {
     pub(crate) data: T,
     /// 节点高度，用于计算平衡因子
     pub(crate) height: isize,
+    /// 子树中的节点个数
+    pub(crate) size: usize,
     /// 左孩子
     pub(crate) lch: AvlTree<T>,
     /// 右孩子
@@ -22,6 +24,10 @@ pub enum RotMod {
     LeftRot,
     /// 右旋
     RightRot,
+    /// 先对左孩子左旋，再右旋
+    LeftRightRot,
+    /// 先对右孩子右旋，再左旋
+    RightLeftRot,
     /// 无需旋转
     NotRot,
 }
@@ -32,19 +38,32 @@ impl<T> AvlNode<T> {
         Self {
             data,
             height: 0,
+            size: 1,
             lch,
             rch,
         }
     }
 
+    /// 根据孩子更新高度与子树大小
+    pub(crate) fn update(&mut self) {
+        self.height = 1 + std::cmp::max(self.lch.height(), self.rch.height());
+        self.size = 1 + self.lch.len() + self.rch.len();
+    }
+
+    /// 平衡因子，即左右子树的高度差
+    pub(crate) fn balance_factor(&self) -> isize {
+        self.lch.height() - self.rch.height()
+    }
+
     /// 获取节点旋转模式
     pub(crate) fn rot_mod(&self) -> RotMod {
-        if self.lch.height() - self.rch.height() > 1 {
-            RotMod::RightRot
-        } else if self.rch.height() - self.lch.height() > 1 {
-            RotMod::LeftRot
-        } else {
-            RotMod::NotRot
+        let child_balance = |t: &AvlTree<T>| t.0.as_ref().map_or(0, |v| v.balance_factor());
+        match self.balance_factor() {
+            2.. if child_balance(&self.lch) < 0 => RotMod::LeftRightRot,
+            2.. => RotMod::RightRot,
+            ..=-2 if child_balance(&self.rch) > 0 => RotMod::RightLeftRot,
+            ..=-2 => RotMod::LeftRot,
+            _ => RotMod::NotRot,
         }
     }
 
@@ -56,9 +75,9 @@ impl<T> AvlNode<T> {
         };
 
         self.rch = AvlTree(right.lch.0.take());
-        self.rch.update_height();
+        self.update();
         right.lch = AvlTree(Some(Box::new(self)));
-        right.height = 1 + std::cmp::max(right.lch.height(), right.rch.height());
+        right.update();
         right
     }
 
@@ -70,9 +89,9 @@ impl<T> AvlNode<T> {
         };
 
         self.lch = AvlTree(left.rch.0.take());
-        self.lch.update_height();
+        self.update();
         left.rch = AvlTree(Some(Box::new(self)));
-        left.height = 1 + std::cmp::max(left.lch.height(), left.rch.height());
+        left.update();
         left
     }
 }
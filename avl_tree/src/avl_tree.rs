@@ -10,25 +10,32 @@
 //! ## Version
 //! 1.0
 
-use std::fmt::{Debug, Display};
+use std::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    ops::{Bound, RangeBounds},
+};
 
 use crate::{
     avl_node::{AvlNode, RotMod},
-    avl_iter::{IntoIter, Iter},
+    avl_iter::{IntoIter, Iter, Range},
 };
 
 /// AVL树
 ///
 /// ## Example
 /// ```rust
-/// use avl_tree::avl_tree::AVLTree;
+/// use avl_tree::avl_tree::AvlTree;
 ///
-/// let mut t = AVLTree::new();
+/// let mut t = AvlTree::new();
 /// t.insert(2);
 /// t.insert(1);
 /// t.insert(3);
 /// assert_eq!(2, t.height());
-/// assert!(t.contains(1));
+/// assert!(t.contains(&1));
+/// assert_eq!(Some(1), t.remove(&1));
+/// assert!(!t.contains(&1));
 /// ```
 #[derive(Debug)]
 pub struct AvlTree<T>(pub(crate) Option<Box<AvlNode<T>>>);
@@ -44,10 +51,20 @@ impl<T> AvlTree<T> {
         self.0.as_ref().map_or(0, |rt| rt.height)
     }
 
-    /// 更新当前节点的高度
-    pub(crate) fn update_height(&mut self) {
+    /// 判断是否为空树
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// 获取数据个数
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |rt| rt.size)
+    }
+
+    /// 更新当前节点的高度与子树大小
+    pub(crate) fn update(&mut self) {
         if let Some(ref mut rt) = self.0 {
-            rt.height = 1 + std::cmp::max(rt.lch.height(), rt.rch.height());
+            rt.update();
         }
     }
 
@@ -61,36 +78,96 @@ impl<T> AvlTree<T> {
         self.0 = self.0.take().map(|v| v.rot_right());
     }
 
-    fn rotate(&mut self, rot_mod: RotMod) {
+    pub(crate) fn rotate(&mut self, rot_mod: RotMod) {
         match rot_mod {
             RotMod::LeftRot => self.rot_left(),
             RotMod::RightRot => self.rot_right(),
-            RotMod::NotRot => self.update_height(),
+            RotMod::LeftRightRot => {
+                if let Some(rt) = &mut self.0 {
+                    rt.lch.rot_left();
+                }
+                self.rot_right();
+            }
+            RotMod::RightLeftRot => {
+                if let Some(rt) = &mut self.0 {
+                    rt.rch.rot_right();
+                }
+                self.rot_left();
+            }
+            RotMod::NotRot => self.update(),
+        }
+    }
+
+    /// 根节点失衡时旋转，并更新高度
+    pub(crate) fn rebalance(&mut self) {
+        if let Some(rt) = &self.0 {
+            let rot_mod = rt.rot_mod();
+            self.rotate(rot_mod);
         }
     }
 
+    /// 删除根节点并返回其数据，有两个孩子时用中序后继替换根节点
+    pub(crate) fn take_root(&mut self) -> Option<T> {
+        let mut rt = self.0.take()?;
+        if rt.lch.0.is_none() {
+            self.0 = rt.rch.0.take();
+        } else if rt.rch.0.is_none() {
+            self.0 = rt.lch.0.take();
+        } else {
+            // 右子树的最小值即中序后继
+            let successor = rt.rch.pop_first()?;
+            let data = std::mem::replace(&mut rt.data, successor);
+            self.0 = Some(rt);
+            self.rebalance();
+            return Some(data);
+        }
+        Some(rt.data)
+    }
+
+    /// 删除并返回最小值
+    pub fn pop_first(&mut self) -> Option<T> {
+        let rt = self.0.as_mut()?;
+        if rt.lch.0.is_none() {
+            return self.take_root();
+        }
+        let data = rt.lch.pop_first();
+        self.rebalance();
+        data
+    }
+
+    /// 删除并返回最大值
+    pub fn pop_last(&mut self) -> Option<T> {
+        let rt = self.0.as_mut()?;
+        if rt.rch.0.is_none() {
+            return self.take_root();
+        }
+        let data = rt.rch.pop_last();
+        self.rebalance();
+        data
+    }
+
+    /// 清空
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
     /// 获取不可变引用迭代器
     pub fn iter(&self) -> Iter<'_, T> {
         let mut iter = Iter::new();
-        let mut curr = self.0.as_deref();
-        while let Some(node) = curr {
-            iter.stack.push(node);
-            curr = node.lch.0.as_deref();
-        }
+        iter.push_left(self.0.as_deref());
+        iter.len = self.len();
         iter
     }
 }
 
-impl<T: PartialOrd> AvlTree<T> {
+impl<T: Ord> AvlTree<T> {
     /// 插入
     pub fn insert(&mut self, data: T) {
         let rot_mod = if let Some(rt) = &mut self.0 {
-            if data < rt.data {
-                rt.lch.insert(data);
-            } else if data > rt.data {
-                rt.rch.insert(data);
-            } else {
-                return; // 重复数据不插入
+            match data.cmp(&rt.data) {
+                Ordering::Less => rt.lch.insert(data),
+                Ordering::Greater => rt.rch.insert(data),
+                Ordering::Equal => return, // 重复数据不插入
             }
             rt.rot_mod()
         } else {
@@ -104,17 +181,261 @@ impl<T: PartialOrd> AvlTree<T> {
         self.rotate(rot_mod);
     }
 
-    /// 判断数据是否存在
-    pub fn contains(&self, data: T) -> bool {
-        self.0.as_ref().is_some_and(|rt| {
-            if data < rt.data {
-                rt.lch.contains(data)
-            } else if data > rt.data {
-                rt.rch.contains(data)
+    /// 删除，返回被删除的数据，不存在时返回None
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let rt = self.0.as_mut()?;
+        let removed = match key.cmp(rt.data.borrow()) {
+            Ordering::Less => rt.lch.remove(key),
+            Ordering::Greater => rt.rch.remove(key),
+            Ordering::Equal => return self.take_root(),
+        };
+        self.rebalance();
+        removed
+    }
+
+    /// 检查二叉搜索树的有序性、各节点记录的高度与子树大小以及平衡因子
+    pub fn check_invariants(&self) -> bool {
+        self.checked_height(None, None).is_some()
+    }
+
+    /// 子树满足不变量且数据都在(lo, hi)内时返回其实际高度
+    fn checked_height(&self, lo: Option<&T>, hi: Option<&T>) -> Option<isize> {
+        let Some(rt) = &self.0 else {
+            return Some(0);
+        };
+        if lo.is_some_and(|lo| rt.data <= *lo) || hi.is_some_and(|hi| rt.data >= *hi) {
+            return None;
+        }
+        let lh = rt.lch.checked_height(lo, Some(&rt.data))?;
+        let rh = rt.rch.checked_height(Some(&rt.data), hi)?;
+        let height = 1 + std::cmp::max(lh, rh);
+        let size = 1 + rt.lch.len() + rt.rch.len();
+        (rt.height == height && rt.size == size && (lh - rh).abs() <= 1).then_some(height)
+    }
+
+    /// 查找与key相等的数据
+    pub fn get<Q>(&self, key: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut cur = self.0.as_ref();
+        while let Some(rt) = cur {
+            cur = match key.cmp(rt.data.borrow()) {
+                Ordering::Less => rt.lch.0.as_ref(),
+                Ordering::Greater => rt.rch.0.as_ref(),
+                Ordering::Equal => return Some(&rt.data),
+            };
+        }
+        None
+    }
+
+    /// 从第一个满足下界的数据开始的迭代器，栈中只保存查找路径上满足下界的节点及其右子树
+    fn seek<Q>(&self, bound: Bound<&Q>) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut iter = Iter::new();
+        let mut curr = self.0.as_deref();
+        while let Some(node) = curr {
+            let above = match bound {
+                Bound::Included(key) => node.data.borrow() >= key,
+                Bound::Excluded(key) => node.data.borrow() > key,
+                Bound::Unbounded => true,
+            };
+            curr = if above {
+                iter.stack.push((&node.data, node.rch.0.as_deref()));
+                iter.len += 1 + node.rch.len();
+                node.lch.0.as_deref()
             } else {
-                true
-            }
-        })
+                node.rch.0.as_deref()
+            };
+        }
+        iter
+    }
+
+    /// 从最后一个满足上界的数据开始反向迭代的迭代器，与`seek`对称
+    fn seek_back<Q>(&self, bound: Bound<&Q>) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut iter = Iter::new();
+        let mut curr = self.0.as_deref();
+        while let Some(node) = curr {
+            let below = match bound {
+                Bound::Included(key) => node.data.borrow() <= key,
+                Bound::Excluded(key) => node.data.borrow() < key,
+                Bound::Unbounded => true,
+            };
+            curr = if below {
+                iter.back.push((&node.data, node.lch.0.as_deref()));
+                iter.len += 1 + node.lch.len();
+                node.rch.0.as_deref()
+            } else {
+                node.lch.0.as_deref()
+            };
+        }
+        iter
+    }
+
+    /// 最后一个满足上界的数据
+    fn last_within<Q>(&self, bound: Bound<&Q>) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut last = None;
+        let mut curr = self.0.as_deref();
+        while let Some(node) = curr {
+            let below = match bound {
+                Bound::Included(key) => node.data.borrow() <= key,
+                Bound::Excluded(key) => node.data.borrow() < key,
+                Bound::Unbounded => true,
+            };
+            curr = if below {
+                last = Some(&node.data);
+                node.rch.0.as_deref()
+            } else {
+                node.lch.0.as_deref()
+            };
+        }
+        last
+    }
+
+    /// 按顺序迭代区间内的数据，下界大于上界时为空
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let (start, end) = (range.start_bound(), range.end_bound());
+        Range {
+            front: self.seek(start),
+            back: self.seek_back(end),
+            len: self.count_in_range((start, end)),
+        }
+    }
+
+    /// 从第一个不小于key的数据开始迭代
+    pub fn lower_bound<Q>(&self, key: &Q) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Included(key))
+    }
+
+    /// 从第一个大于key的数据开始迭代
+    pub fn upper_bound<Q>(&self, key: &Q) -> Iter<'_, T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(Bound::Excluded(key))
+    }
+
+    /// 不大于key的最大数据
+    pub fn floor<Q>(&self, key: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.last_within(Bound::Included(key))
+    }
+
+    /// 不小于key的最小数据
+    pub fn ceiling<Q>(&self, key: &Q) -> Option<&T>
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.lower_bound(key).next()
+    }
+
+    /// 按顺序的第k个数据，k从0开始
+    pub fn nth(&self, mut k: usize) -> Option<&T> {
+        let mut curr = self.0.as_deref();
+        while let Some(node) = curr {
+            let left = node.lch.len();
+            curr = match k.cmp(&left) {
+                Ordering::Less => node.lch.0.as_deref(),
+                Ordering::Equal => return Some(&node.data),
+                Ordering::Greater => {
+                    k -= left + 1;
+                    node.rch.0.as_deref()
+                }
+            };
+        }
+        None
+    }
+
+    /// 小于key的数据个数，inclusive为true时也计入等于key的数据
+    fn count_before<Q>(&self, key: &Q, inclusive: bool) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut count = 0;
+        let mut curr = self.0.as_deref();
+        while let Some(node) = curr {
+            let before = match key.cmp(node.data.borrow()) {
+                Ordering::Less => false,
+                Ordering::Equal => inclusive,
+                Ordering::Greater => true,
+            };
+            curr = if before {
+                count += node.lch.len() + 1;
+                node.rch.0.as_deref()
+            } else {
+                node.lch.0.as_deref()
+            };
+        }
+        count
+    }
+
+    /// 小于key的数据个数，即key插入后的下标
+    pub fn rank<Q>(&self, key: &Q) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_before(key, false)
+    }
+
+    /// 区间内的数据个数，下界大于上界时为0
+    pub fn count_in_range<Q, R>(&self, range: R) -> usize
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.count_before(key, false),
+            Bound::Excluded(key) => self.count_before(key, true),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.count_before(key, true),
+            Bound::Excluded(key) => self.count_before(key, false),
+            Bound::Unbounded => self.len(),
+        };
+        end.saturating_sub(start)
+    }
+
+    /// 判断数据是否存在
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
     }
 }
 
@@ -146,6 +467,8 @@ impl<T> IntoIterator for AvlTree<T> {
 
 #[cfg(test)]
 mod tests {
+    use crate::test_util::xorshift;
+
     use super::*;
 
     #[test]
@@ -162,7 +485,317 @@ mod tests {
 
         println!("{}", t);
 
-        assert!(t.contains(10));
-        assert!(!t.contains(100));
+        assert!(t.contains(&10));
+        assert!(!t.contains(&100));
+    }
+
+    #[test]
+    fn test_remove() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [50, 30, 70, 20, 40, 60, 80, 35, 45, 65] {
+            t.insert(i);
+        }
+
+        // When
+        let leaf = t.remove(&20);
+        let one_child = t.remove(&60);
+        let two_children = t.remove(&30);
+        let root = t.remove(&50);
+        let missing = t.remove(&100);
+
+        // Then
+        assert_eq!(Some(20), leaf);
+        assert_eq!(Some(60), one_child);
+        assert_eq!(Some(30), two_children);
+        assert_eq!(Some(50), root);
+        assert_eq!(None, missing);
+        let nums: Vec<_> = t.iter().copied().collect();
+        assert_eq!(vec![35, 40, 45, 65, 70, 80], nums);
+        assert!(!t.contains(&50));
+        assert!(t.contains(&65));
+    }
+
+    #[test]
+    fn test_remove_many() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in 0..128 {
+            t.insert(i);
+        }
+
+        // When
+        for i in (0..128).filter(|i| i % 3 != 0) {
+            assert_eq!(Some(i), t.remove(&i));
+        }
+
+        // Then
+        let nums: Vec<_> = t.iter().copied().collect();
+        assert_eq!((0..128).step_by(3).collect::<Vec<_>>(), nums);
+        assert!(t.check_invariants());
+        // 43个节点的AVL树高度不超过1.44·log2(45)
+        assert!(t.height() <= 7);
+        for i in (0..128).step_by(3) {
+            assert_eq!(Some(i), t.remove(&i));
+        }
+        assert_eq!(0, t.height());
+        assert!(t.iter().next().is_none());
+    }
+
+    #[test]
+    fn test_pop_and_clear() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [5, 3, 8, 1, 4, 7, 9, 2, 6] {
+            t.insert(i);
+        }
+
+        // When
+        let first = t.pop_first();
+        let last = t.pop_last();
+        let second = t.pop_first();
+
+        // Then
+        assert_eq!(Some(1), first);
+        assert_eq!(Some(9), last);
+        assert_eq!(Some(2), second);
+        assert_eq!(vec![3, 4, 5, 6, 7, 8], t.iter().copied().collect::<Vec<_>>());
+        assert!(!t.is_empty());
+        t.clear();
+        assert!(t.is_empty());
+        assert_eq!(None, t.pop_first());
+        assert_eq!(None, t.pop_last());
+        assert_eq!(0, t.height());
+    }
+
+    #[test]
+    fn test_double_rotation() {
+        // Given
+        let mut lr = AvlTree::new();
+        let mut rl = AvlTree::new();
+
+        // When
+        for i in [3, 1, 2] {
+            lr.insert(i);
+        }
+        for i in [1, 3, 2] {
+            rl.insert(i);
+        }
+
+        // Then
+        for t in [&lr, &rl] {
+            assert_eq!(2, t.height());
+            assert_eq!(Some(&2), t.0.as_ref().map(|rt| &rt.data));
+            assert!(t.check_invariants());
+        }
+    }
+
+    #[test]
+    fn test_random_invariants() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..50 {
+            // Given
+            let mut t = AvlTree::new();
+            let mut nums = Vec::new();
+
+            // When
+            for _ in 0..200 {
+                let x = xorshift(&mut state) % 500;
+                t.insert(x);
+                nums.push(x);
+                assert!(t.check_invariants());
+            }
+            for _ in 0..100 {
+                let x = xorshift(&mut state) % 500;
+                assert_eq!(nums.contains(&x), t.remove(&x).is_some());
+                nums.retain(|&v| v != x);
+                assert!(t.check_invariants());
+            }
+
+            // Then
+            nums.sort_unstable();
+            nums.dedup();
+            assert_eq!(nums, t.iter().copied().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_check_invariants_detects_violation() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [2, 1, 3] {
+            t.insert(i);
+        }
+        assert!(t.check_invariants());
+
+        // When
+        if let Some(rt) = &mut t.0 {
+            rt.lch.0.as_mut().unwrap().data = 5;
+        }
+
+        // Then
+        assert!(!t.check_invariants());
+    }
+
+    #[test]
+    fn test_borrowed_lookup() {
+        // Given
+        let mut t = AvlTree::new();
+        for s in ["pear", "apple", "fig", "banana"] {
+            t.insert(s.to_string());
+        }
+
+        // When
+        let found = t.get("fig").cloned();
+        let missing = t.get("kiwi").cloned();
+        let removed = t.remove("apple");
+
+        // Then
+        assert_eq!(Some("fig".to_string()), found);
+        assert_eq!(None, missing);
+        assert_eq!(Some("apple".to_string()), removed);
+        assert!(t.contains("pear"));
+        assert!(!t.contains("apple"));
+    }
+
+    #[test]
+    fn test_bounds() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [10, 20, 30, 40, 50] {
+            t.insert(i);
+        }
+
+        // When
+        let from_lower: Vec<_> = t.lower_bound(&30).copied().collect();
+        let from_upper: Vec<_> = t.upper_bound(&30).copied().collect();
+        let between: Vec<_> = t.lower_bound(&25).copied().collect();
+
+        // Then
+        assert_eq!(vec![30, 40, 50], from_lower);
+        assert_eq!(vec![40, 50], from_upper);
+        assert_eq!(vec![30, 40, 50], between);
+        assert_eq!(0, t.upper_bound(&50).count());
+        assert_eq!(Some(&20), t.floor(&25));
+        assert_eq!(Some(&30), t.floor(&30));
+        assert_eq!(None, t.floor(&5));
+        assert_eq!(Some(&30), t.ceiling(&25));
+        assert_eq!(Some(&50), t.ceiling(&50));
+        assert_eq!(None, t.ceiling(&55));
+    }
+
+    #[test]
+    fn test_random_range() {
+        let mut state = 0x5851_f42d_4c95_7f2d;
+        // Given
+        let mut t = AvlTree::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for _ in 0..300 {
+            let x = xorshift(&mut state) % 1000;
+            t.insert(x);
+            expected.insert(x);
+        }
+
+        for _ in 0..200 {
+            // When
+            let a = xorshift(&mut state) % 1100;
+            let b = a + xorshift(&mut state) % 200;
+            let got: Vec<_> = t.range(a..b).collect();
+            let got_inclusive: Vec<_> = t.range((Bound::Excluded(a), Bound::Included(b))).collect();
+
+            // Then
+            assert_eq!(expected.range(a..b).collect::<Vec<_>>(), got);
+            assert_eq!(
+                expected
+                    .range((Bound::Excluded(a), Bound::Included(b)))
+                    .collect::<Vec<_>>(),
+                got_inclusive
+            );
+            assert_eq!(expected.range(..=a).next_back(), t.floor(&a));
+            assert_eq!(expected.range(a..).next(), t.ceiling(&a));
+        }
+    }
+
+    #[test]
+    fn test_len() {
+        // Given
+        let mut t = AvlTree::new();
+        assert!(t.is_empty());
+
+        // When
+        for i in 0..100 {
+            t.insert(i % 37);
+        }
+        t.remove(&5);
+        t.pop_first();
+
+        // Then
+        assert_eq!(35, t.len());
+        assert!(!t.is_empty());
+        assert_eq!(35, t.iter().len());
+        assert_eq!(35, t.into_iter().len());
+    }
+
+    #[test]
+    fn test_order_statistics() {
+        // Given
+        let mut t = AvlTree::new();
+        for i in [50, 10, 40, 20, 30] {
+            t.insert(i);
+        }
+
+        // When
+        let nth: Vec<_> = (0..6).map(|k| t.nth(k).copied()).collect();
+        let ranks: Vec<_> = [5, 10, 25, 50, 55].iter().map(|x| t.rank(x)).collect();
+
+        // Then
+        assert_eq!(
+            vec![Some(10), Some(20), Some(30), Some(40), Some(50), None],
+            nth
+        );
+        assert_eq!(vec![0, 0, 2, 4, 5], ranks);
+        assert_eq!(3, t.count_in_range(20..50));
+        assert_eq!(4, t.count_in_range(20..=50));
+        assert_eq!(2, t.count_in_range(..30));
+        assert_eq!(5, t.count_in_range(..));
+        assert_eq!(0, t.count_in_range(31..40));
+        assert_eq!(
+            0,
+            t.count_in_range((Bound::Included(40), Bound::Excluded(20)))
+        );
+    }
+
+    #[test]
+    fn test_random_order_statistics() {
+        let mut state = 0x0bad_5eed_dead_beef;
+        // Given
+        let mut t = AvlTree::new();
+        let mut expected = std::collections::BTreeSet::new();
+        for _ in 0..500 {
+            let x = xorshift(&mut state) % 2000;
+            if xorshift(&mut state).is_multiple_of(4) {
+                t.remove(&x);
+                expected.remove(&x);
+            } else {
+                t.insert(x);
+                expected.insert(x);
+            }
+        }
+        let sorted: Vec<_> = expected.iter().copied().collect();
+
+        // When
+        for (k, x) in sorted.iter().enumerate() {
+            // Then
+            assert_eq!(Some(x), t.nth(k));
+            assert_eq!(k, t.rank(x));
+        }
+        assert_eq!(None, t.nth(sorted.len()));
+        for _ in 0..200 {
+            let a = xorshift(&mut state) % 2100;
+            let b = a + xorshift(&mut state) % 300;
+            assert_eq!(expected.range(a..b).count(), t.count_in_range(a..b));
+            assert_eq!(expected.range(a..=b).count(), t.count_in_range(a..=b));
+            assert_eq!(sorted.partition_point(|&v| v < a), t.rank(&a));
+        }
     }
 }
@@ -1,3 +1,7 @@
 pub mod avl_node;
 pub mod avl_tree;
-pub mod avl_iter;
\ No newline at end of file
+pub mod avl_iter;
+pub mod avl_map;
+
+#[cfg(test)]
+mod test_util;
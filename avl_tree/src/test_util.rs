@@ -0,0 +1,10 @@
+//! ## Brief
+//! 各模块测试共用的辅助函数
+
+/// 测试用的xorshift伪随机数
+pub(crate) fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
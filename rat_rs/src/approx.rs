@@ -0,0 +1,259 @@
+//! approx提供区间内最简分数的查找，用于把分数化简为便于展示的近似值
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//!
+//! let pi = FractionU32::with_non_negative(355, 113).unwrap();
+//! let tol = FractionU32::with_non_negative(1, 100).unwrap();
+//! assert_eq!(pi.simplify_within(tol).unwrap(), FractionU32::with_non_negative(22, 7).unwrap());
+//! ```
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 在Stern–Brocot树上查找区间内分母最小的非负分数，要求区间非空且不包含0以下的部分
+fn simplest_non_negative(lo: Wide, lo_open: bool, hi: Wide, hi_open: bool) -> (u128, u128) {
+    let (ln, ld) = (lo.n as u128, lo.d as u128);
+    let (hn, hd) = (hi.n as u128, hi.d as u128);
+    // 左边界 p0/q0 始终在区间下方，右边界 p1/q1 始终在区间上方
+    let (mut p0, mut q0, mut p1, mut q1) = (0_u128, 1_u128, 1_u128, 0_u128);
+    loop {
+        let (p, q) = (p0 + p1, q0 + q1);
+        let below = match (p * ld).cmp(&(ln * q)) {
+            core::cmp::Ordering::Less => true,
+            core::cmp::Ordering::Equal => lo_open,
+            core::cmp::Ordering::Greater => false,
+        };
+        let above = match (p * hd).cmp(&(hn * q)) {
+            core::cmp::Ordering::Greater => true,
+            core::cmp::Ordering::Equal => hi_open,
+            core::cmp::Ordering::Less => false,
+        };
+        if below {
+            // 一次性向右走尽可能多步，避免逐步下降
+            let num = ln * q0 - p0 * ld;
+            let den = p1 * ld - ln * q1;
+            let k = if lo_open { num / den } else { (num - 1) / den };
+            p0 += k * p1;
+            q0 += k * q1;
+        } else if above {
+            let num = p1 * hd - hn * q1;
+            let den = hn * q0 - p0 * hd;
+            let k = if hi_open { num / den } else { (num - 1) / den };
+            p1 += k * p0;
+            q1 += k * q0;
+        } else {
+            return (p, q);
+        }
+    }
+}
+
+/// 查找区间内分母最小的分数，区间端点的开闭由参数指定
+fn simplest<T>(
+    lo: Wide,
+    lo_open: bool,
+    hi: Wide,
+    hi_open: bool,
+) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    match lo.cmp(&hi) {
+        core::cmp::Ordering::Greater => return Err(RationalError::EmptyInterval),
+        core::cmp::Ordering::Equal if lo_open || hi_open => {
+            return Err(RationalError::EmptyInterval)
+        }
+        _ => {}
+    }
+    let zero = Wide::ZERO;
+    let above_lo = match zero.cmp(&lo) {
+        core::cmp::Ordering::Greater => true,
+        core::cmp::Ordering::Equal => !lo_open,
+        core::cmp::Ordering::Less => false,
+    };
+    let below_hi = match zero.cmp(&hi) {
+        core::cmp::Ordering::Less => true,
+        core::cmp::Ordering::Equal => !hi_open,
+        core::cmp::Ordering::Greater => false,
+    };
+    let ((n, d), sign) = if above_lo && below_hi {
+        ((0, 1), FractionSign::NonNegative)
+    } else if lo.n >= 0 {
+        (
+            simplest_non_negative(lo, lo_open, hi, hi_open),
+            FractionSign::NonNegative,
+        )
+    } else {
+        (
+            simplest_non_negative(hi.neg(), hi_open, lo.neg(), lo_open),
+            FractionSign::Negative,
+        )
+    };
+    let numer = u64::try_from(n)
+        .ok()
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or(RationalError::NumeratorOverflow)?;
+    let denom = u64::try_from(d)
+        .ok()
+        .and_then(|d| T::try_from(d).ok())
+        .ok_or(RationalError::DenominatorOverflow)?;
+    Fraction::new(numer, denom, sign)
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 闭区间[lo, hi]内分母最小的分数，分母相同时取绝对值最小者
+    pub fn simplest_between(lo: Self, hi: Self) -> Result<Self, RationalError> {
+        simplest(
+            Wide::from_fraction(&lo),
+            false,
+            Wide::from_fraction(&hi),
+            false,
+        )
+    }
+
+    /// 开区间(lo, hi)内分母最小的分数，分母相同时取绝对值最小者
+    pub fn simplest_strictly_between(lo: Self, hi: Self) -> Result<Self, RationalError> {
+        simplest(
+            Wide::from_fraction(&lo),
+            true,
+            Wide::from_fraction(&hi),
+            true,
+        )
+    }
+
+    /// 用[self - tolerance, self + tolerance]内最简的分数替换自身，tolerance取绝对值
+    pub fn simplify_within(self, tolerance: Self) -> Result<Self, RationalError> {
+        let x = Wide::from_fraction(&self);
+        let tol = Wide::from_fraction(&tolerance).abs();
+        simplest(x.sub(tol)?, false, x.add(tol)?, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        frac::{FractionU32, FractionU8},
+        test_util::frac,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_simplest_between() {
+        assert_eq!(
+            Fraction::simplest_between(frac(1, 3), frac(1, 2)),
+            Ok(frac(1, 2))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(3, 10), frac(4, 10)),
+            Ok(frac(1, 3))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(7, 2), frac(7, 2)),
+            Ok(frac(7, 2))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(5, 4), frac(7, 3)),
+            Ok(frac(2, 1))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(-1, 2), frac(1, 3)),
+            Ok(frac(0, 1))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(0, 1), frac(1, 3)),
+            Ok(frac(0, 1))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(-4, 10), frac(-3, 10)),
+            Ok(frac(-1, 3))
+        );
+        assert_eq!(
+            Fraction::simplest_between(frac(1, 2), frac(1, 3)),
+            Err(RationalError::EmptyInterval)
+        );
+    }
+
+    #[test]
+    fn test_simplest_strictly_between() {
+        assert_eq!(
+            Fraction::simplest_strictly_between(frac(1, 3), frac(1, 2)),
+            Ok(frac(2, 5))
+        );
+        assert_eq!(
+            Fraction::simplest_strictly_between(frac(0, 1), frac(1, 3)),
+            Ok(frac(1, 4))
+        );
+        assert_eq!(
+            Fraction::simplest_strictly_between(frac(1, 1), frac(3, 1)),
+            Ok(frac(2, 1))
+        );
+        assert_eq!(
+            Fraction::simplest_strictly_between(frac(-1, 1), frac(0, 1)),
+            Ok(frac(-1, 2))
+        );
+        assert_eq!(
+            Fraction::simplest_strictly_between(frac(1, 2), frac(1, 2)),
+            Err(RationalError::EmptyInterval)
+        );
+        assert_eq!(
+            FractionU8::simplest_strictly_between(
+                FractionU8::with_non_negative(254, 255).unwrap(),
+                FractionU8::from(1_u8)
+            ),
+            Err(RationalError::DenominatorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_simplest_between_matches_brute_force() {
+        for lo_n in -12_i64..=12 {
+            for hi_n in lo_n..=12 {
+                let (lo, hi) = (frac(lo_n, 7), frac(hi_n, 5));
+                let expected = (1_u32..=35)
+                    .flat_map(|d| (0_i64..=100).flat_map(move |n| [frac(n, d), frac(-n, d)]))
+                    .find(|f| *f >= lo && *f <= hi);
+                assert_eq!(Fraction::simplest_between(lo, hi).ok(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_simplify_within() {
+        let pi = frac(355, 113);
+        assert_eq!(pi.simplify_within(frac(1, 100)), Ok(frac(22, 7)));
+        assert_eq!(pi.simplify_within(frac(-1, 5)), Ok(frac(3, 1)));
+        assert_eq!(pi.simplify_within(frac(0, 1)), Ok(pi));
+        assert_eq!(
+            frac(-355, 113).simplify_within(frac(1, 100)),
+            Ok(frac(-22, 7))
+        );
+        assert_eq!(
+            FractionU32::with_non_negative(u32::MAX - 1, u32::MAX)
+                .unwrap()
+                .simplify_within(FractionU32::with_non_negative(1, u32::MAX).unwrap()),
+            Ok(frac(1, 1))
+        );
+        // 区间端点的分母接近2^64，交叉相乘会超出i128
+        assert_eq!(
+            FractionU32::with_non_negative(u32::MAX - 1, u32::MAX)
+                .unwrap()
+                .simplify_within(FractionU32::with_non_negative(1, u32::MAX - 2).unwrap()),
+            Ok(frac(1, 1))
+        );
+        assert_eq!(
+            FractionU32::with_negative(u32::MAX - 1, u32::MAX)
+                .unwrap()
+                .simplify_within(FractionU32::with_non_negative(1, u32::MAX - 2).unwrap()),
+            Ok(frac(-1, 1))
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! batch提供分数切片上的批量运算。`rescale_to`把切片就地改写到公分母上并返回`Rescaled`，
+//! 之后的`add_assign`/`mul_scalar`/`div_scalar`在分子上直接运算而不求最大公约数，
+//! `Rescaled`结束时统一约分一次，避免逐个调用checked运算时每步都约分的开销
+//!
+//! 未约分的中间结果只存在于`Rescaled`独占借用的切片中，外部观察到的分数总是既约的
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::batch;
+//! use rat_rs::frac::FractionU32;
+//!
+//! let mut xs = [(1, 2), (1, 3), (5, 6)].map(|(n, d)| FractionU32::with_non_negative(n, d).unwrap());
+//! let denom = batch::common_denominator(&xs).unwrap();
+//! assert_eq!(denom, 6);
+//! let mut ones = [FractionU32::from(1_u32); 3];
+//! let mut acc = batch::rescale_to(&mut xs, denom).unwrap();
+//! acc.add_assign(&batch::rescale_to(&mut ones, denom).unwrap()).unwrap();
+//! acc.finish();
+//! assert_eq!(xs.map(|x| (x.numer(), x.denom())), [(3, 2), (4, 3), (11, 6)]);
+//! assert_eq!(batch::sum_slice(&xs).unwrap(), FractionU32::with_non_negative(14, 3).unwrap());
+//! ```
+
+use crate::{
+    error::RationalError,
+    frac::{gcd, Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 只求一次最大公约数，把±n/d约分为Fraction
+fn reduce_once<T>(n: i128, d: u128) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let d = i128::try_from(d).map_err(|_| RationalError::DenominatorOverflow)?;
+    Wide::reduced(n, d).to_coprime_fraction()
+}
+
+/// 不约分地构造±n/d，放不下T时才约分一次
+fn unreduced_or_reduce<T>(n: i128, d: u128) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let numer = u64::try_from(n.unsigned_abs())
+        .ok()
+        .and_then(|n| T::try_from(n).ok());
+    let denom = u64::try_from(d).ok().and_then(|d| T::try_from(d).ok());
+    let (Some(numer), Some(denom)) = (numer, denom) else {
+        return reduce_once(n, d);
+    };
+    let sign = if n < 0 {
+        FractionSign::Negative
+    } else {
+        FractionSign::NonNegative
+    };
+    Ok(Fraction { numer, denom, sign })
+}
+
+/// 所有分母的最小公倍数，空切片返回1，超出T时返回DenominatorOverflow
+pub fn common_denominator<T>(xs: &[Fraction<T>]) -> Result<T, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let mut lcm = 1_u64;
+    for x in xs {
+        let d = u64::from(x.denom());
+        // 已经是公倍数时无需求最大公约数
+        if lcm.is_multiple_of(d) {
+            continue;
+        }
+        lcm = (lcm / gcd(lcm, d))
+            .checked_mul(d)
+            .filter(|&l| l <= T::MAX)
+            .ok_or(RationalError::DenominatorOverflow)?;
+    }
+    T::try_from(lcm).map_err(|_| RationalError::DenominatorOverflow)
+}
+
+/// 把各分数就地改写为以denom为分母的形式，不约分，
+/// 某个分母不整除denom时返回Inexact，分子超出T时返回NumeratorOverflow，出错时切片保持原值
+pub fn rescale_to<T>(xs: &mut [Fraction<T>], denom: T) -> Result<Rescaled<'_, T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let target = u64::from(denom);
+    // 先建立Rescaled，出错返回时由drop把已改写的元素约分回原值
+    let rescaled = Rescaled { xs };
+    for x in rescaled.xs.iter_mut() {
+        let d = u64::from(x.denom());
+        if target == 0 || !target.is_multiple_of(d) {
+            return Err(RationalError::Inexact);
+        }
+        x.numer = T::try_from(u64::from(x.numer()) * (target / d))
+            .map_err(|_| RationalError::NumeratorOverflow)?;
+        x.denom = denom;
+    }
+    Ok(rescaled)
+}
+
+/// 逐个约分，每个元素求一次最大公约数
+fn reduce_slice<T>(xs: &mut [Fraction<T>])
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    for x in xs {
+        let (n, d) = (u64::from(x.numer()), u64::from(x.denom()));
+        let g = gcd(n, d);
+        if g != 1 {
+            // 约分后只会变小，一定能放入T
+            x.numer = T::try_from(n / g).ok().unwrap();
+            x.denom = T::try_from(d / g).ok().unwrap();
+        }
+    }
+}
+
+/// 求和，先通分再累加分子，最后只约分一次
+pub fn sum_slice<T>(xs: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let denom = u64::from(common_denominator(xs)?);
+    let mut numer = 0_i128;
+    for x in xs {
+        numer = numer
+            .checked_add(x.signed_numer() * i128::from(denom / u64::from(x.denom())))
+            .ok_or(RationalError::NumeratorOverflow)?;
+    }
+    reduce_once(numer, u128::from(denom))
+}
+
+/// 由`rescale_to`得到的公分母切片，独占借用原切片，其中的元素可能未约分；
+/// `finish`或drop时逐个约分。用`mem::forget`跳过drop会使切片保持未约分
+pub struct Rescaled<'a, T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    xs: &'a mut [Fraction<T>],
+}
+
+impl<T> Rescaled<'_, T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 逐元素`self[i] += rhs[i]`，分母相同时只相加分子而不约分，分母不同时约分一次，
+    /// 长度不一致时返回OutOfRange，出错时之前的元素已经更新
+    pub fn add_assign(&mut self, rhs: &Rescaled<'_, T>) -> Result<(), RationalError> {
+        if self.xs.len() != rhs.xs.len() {
+            return Err(RationalError::OutOfRange);
+        }
+        for (a, b) in self.xs.iter_mut().zip(rhs.xs.iter()) {
+            let (ad, bd) = (u64::from(a.denom()), u64::from(b.denom()));
+            *a = if ad == bd {
+                // 分母相同，直接相加分子
+                unreduced_or_reduce(a.signed_numer() + b.signed_numer(), u128::from(ad))?
+            } else {
+                reduce_once(
+                    a.signed_numer() * i128::from(bd) + b.signed_numer() * i128::from(ad),
+                    u128::from(ad) * u128::from(bd),
+                )?
+            };
+        }
+        Ok(())
+    }
+
+    /// 逐元素乘以k，不约分，公分母仍然相同，乘积放不下T的元素约分一次，出错时之前的元素已经更新
+    pub fn mul_scalar(&mut self, k: Fraction<T>) -> Result<(), RationalError> {
+        self.scale(k.signed_numer(), u128::from(u64::from(k.denom())))
+    }
+
+    /// 逐元素除以k，与`checked_div`一样，k为零时返回DivideByZero
+    pub fn div_scalar(&mut self, k: Fraction<T>) -> Result<(), RationalError> {
+        let kn = k.signed_numer();
+        if kn == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        let kd = i128::from(u64::from(k.denom()));
+        self.scale(kd * kn.signum(), kn.unsigned_abs())
+    }
+
+    /// 逐元素乘以n/d，d > 0
+    fn scale(&mut self, n: i128, d: u128) -> Result<(), RationalError> {
+        for x in self.xs.iter_mut() {
+            *x = unreduced_or_reduce(x.signed_numer() * n, u128::from(u64::from(x.denom())) * d)?;
+        }
+        Ok(())
+    }
+
+    /// 结束批量运算，逐个约分
+    pub fn finish(self) {}
+}
+
+impl<T> Drop for Rescaled<'_, T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn drop(&mut self) {
+        reduce_slice(self.xs);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        frac::{FractionU32, FractionU8},
+        test_util::frac,
+    };
+
+    use super::*;
+
+    /// 分子分母取自小范围网格的分数
+    fn grid() -> impl Iterator<Item = FractionU32> {
+        (1..12_u32).flat_map(|d| {
+            (0..25_u32).map(move |n| {
+                if n % 3 == 0 {
+                    FractionU32::with_negative(n, d).unwrap()
+                } else {
+                    FractionU32::with_non_negative(n, d).unwrap()
+                }
+            })
+        })
+    }
+
+    #[test]
+    fn test_common_denominator() {
+        let xs =
+            [(1, 4), (1, 6), (3, 10)].map(|(n, d)| FractionU32::with_non_negative(n, d).unwrap());
+        assert_eq!(common_denominator(&xs), Ok(60));
+        assert_eq!(common_denominator::<u32>(&[]), Ok(1));
+        let ys = [(1, 16), (1, 17)].map(|(n, d)| FractionU8::with_non_negative(n, d).unwrap());
+        assert_eq!(
+            common_denominator(&ys),
+            Err(RationalError::DenominatorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_rescale_round_trip() {
+        let xs = [(1, 4), (-1, 6), (3, 10), (0, 1)].map(|(n, d)| frac(n, d));
+        let mut scaled = xs;
+        let rescaled = rescale_to(&mut scaled, 120).unwrap();
+        assert!(rescaled
+            .xs
+            .iter()
+            .map(|x| (x.signed_numer(), x.denom()))
+            .eq([(30, 120), (-20, 120), (36, 120), (0, 120)]));
+        rescaled.finish();
+        assert_eq!(scaled, xs);
+        // 前两个元素改写后才发现3/10不整除12，出错时切片保持原值
+        assert_eq!(
+            rescale_to(&mut scaled, 12).err(),
+            Some(RationalError::Inexact)
+        );
+        assert_eq!(scaled, xs);
+        let mut ys = [FractionU8::with_non_negative(200, 3).unwrap()];
+        assert_eq!(
+            rescale_to(&mut ys, 6).err(),
+            Some(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(ys, [FractionU8::with_non_negative(200, 3).unwrap()]);
+    }
+
+    #[test]
+    fn test_shared_denominator_accumulation() {
+        // 公分母上的累加与数乘不约分，最后统一约分
+        let mut acc = [(1, 2), (-1, 3), (0, 1)].map(|(n, d)| frac(n, d));
+        let rows = [
+            [(1, 6), (1, 2), (-5, 6)],
+            [(1, 3), (-1, 6), (1, 1)],
+            [(-1, 2), (1, 3), (1, 6)],
+        ]
+        .map(|row| row.map(|(n, d)| frac(n, d)));
+        let mut expected = acc;
+        for row in &rows {
+            for (e, &x) in expected.iter_mut().zip(row) {
+                *e = e.checked_add(x).unwrap();
+            }
+        }
+        let mut shared = rescale_to(&mut acc, 6).unwrap();
+        for row in &rows {
+            let mut row = *row;
+            shared
+                .add_assign(&rescale_to(&mut row, 6).unwrap())
+                .unwrap();
+        }
+        assert!(shared.xs.iter().all(|x| x.denom() == 6));
+        shared.mul_scalar(frac(-3, 5)).unwrap();
+        assert!(shared.xs.iter().all(|x| x.denom() == 30));
+        shared.div_scalar(frac(-1, 7)).unwrap();
+        assert!(shared.xs.iter().all(|x| x.denom() == 30));
+        shared.finish();
+        let k = frac(21, 5);
+        assert_eq!(acc, expected.map(|e| e.checked_mul(k).unwrap()));
+    }
+
+    #[test]
+    fn test_matches_checked_ops() {
+        let xs: [FractionU32; 275] = core::array::from_fn({
+            let mut it = grid();
+            move |_| it.next().unwrap()
+        });
+        let denom = common_denominator(&xs).unwrap();
+        let mut rhs = xs;
+        rhs.reverse();
+        let mut acc = xs;
+        let mut shared = rescale_to(&mut acc, denom).unwrap();
+        shared
+            .add_assign(&rescale_to(&mut rhs, denom).unwrap())
+            .unwrap();
+        shared.finish();
+        for i in 0..xs.len() {
+            assert_eq!(acc[i], xs[i].checked_add(rhs[i]).unwrap());
+        }
+        let k = FractionU32::with_negative(6, 35).unwrap();
+        let mut scaled = xs;
+        let mut shared = rescale_to(&mut scaled, denom).unwrap();
+        shared.mul_scalar(k).unwrap();
+        shared.finish();
+        for i in 0..xs.len() {
+            assert_eq!(scaled[i], xs[i].checked_mul(k).unwrap());
+        }
+        let mut divided = xs;
+        rescale_to(&mut divided, denom)
+            .unwrap()
+            .div_scalar(k)
+            .unwrap();
+        for i in 0..xs.len() {
+            assert_eq!(divided[i], xs[i].checked_div(k).unwrap());
+        }
+        let expected = xs
+            .iter()
+            .try_fold(FractionU32::from(0_u32), |acc, &x| acc.checked_add(x));
+        assert_eq!(sum_slice(&xs), expected);
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut xs = [FractionU8::from(200_u8)];
+        let mut ys = [FractionU8::from(100_u8)];
+        let mut shared = rescale_to(&mut xs, 1).unwrap();
+        assert_eq!(
+            shared.add_assign(&rescale_to(&mut ys, 1).unwrap()),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            shared.mul_scalar(FractionU8::with_non_negative(1, 255).unwrap()),
+            Ok(())
+        );
+        // 200/255未约分也能放入u8
+        assert_eq!((shared.xs[0].numer(), shared.xs[0].denom()), (200, 255));
+        assert_eq!(
+            shared.div_scalar(FractionU8::from(0_u8)),
+            Err(RationalError::DivideByZero)
+        );
+        shared.finish();
+        assert_eq!(xs, [FractionU8::with_non_negative(40, 51).unwrap()]);
+    }
+}
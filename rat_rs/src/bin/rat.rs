@@ -0,0 +1,47 @@
+//! rat是基于expr模块的精确分数计算器REPL
+//!
+//! 每行输入一个表达式或赋值语句`name = expr`，上一次的结果保存在变量`ans`中，
+//! 输入`exit`或`quit`退出
+
+use std::io::{self, BufRead, Write};
+
+use rat_rs::expr::Context;
+
+fn main() -> io::Result<()> {
+    let mut ctx = Context::<u32>::new();
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+    let mut line = String::new();
+    loop {
+        write!(stdout, "> ")?;
+        stdout.flush()?;
+        line.clear();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let src = line.trim_end();
+        match src.trim() {
+            "" => continue,
+            "exit" | "quit" => break,
+            _ => {}
+        }
+        match ctx.execute(src) {
+            Ok(v) => {
+                ctx.set("ans", v);
+                writeln!(stdout, "{v}")?;
+            }
+            Err(e) => {
+                // 用^标出出错的区间，前面的2列是提示符
+                let width = (e.span.end - e.span.start).max(1);
+                writeln!(
+                    stdout,
+                    "  {}{}",
+                    " ".repeat(e.span.start),
+                    "^".repeat(width)
+                )?;
+                writeln!(stdout, "error: {e}")?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,343 @@
+//! complex提供实部与虚部均为分数的复数，即高斯有理数，所有运算都归结为分数的checked运算，结果精确
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::complex::ComplexFraction;
+//!
+//! let z: ComplexFraction<u32> = "1/2 - 3/4i".parse().unwrap();
+//! let w = z * z.conj();
+//! assert_eq!(w.to_string(), "13/16");
+//! assert_eq!(z.norm().unwrap().to_string(), "13/16");
+//! assert_eq!((z / z).to_string(), "1");
+//! assert_eq!(z.checked_pow(2).unwrap().to_string(), "-5/16 - 3/4i");
+//! ```
+
+use core::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 复数re + im·i
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ComplexFraction<T> {
+    re: Fraction<T>,
+    im: Fraction<T>,
+}
+
+impl<T> ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    pub fn new(re: Fraction<T>, im: Fraction<T>) -> Self {
+        Self { re, im }
+    }
+
+    /// 实部
+    pub fn re(&self) -> Fraction<T> {
+        self.re
+    }
+
+    /// 虚部
+    pub fn im(&self) -> Fraction<T> {
+        self.im
+    }
+
+    /// 共轭复数
+    pub fn conj(&self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// 范数re² + im²，即模的平方
+    pub fn norm(&self) -> Result<Fraction<T>, RationalError> {
+        self.re
+            .checked_mul(self.re)?
+            .checked_add(self.im.checked_mul(self.im)?)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, RationalError> {
+        Ok(Self::new(
+            self.re.checked_add(rhs.re)?,
+            self.im.checked_add(rhs.im)?,
+        ))
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, RationalError> {
+        Ok(Self::new(
+            self.re.checked_sub(rhs.re)?,
+            self.im.checked_sub(rhs.im)?,
+        ))
+    }
+
+    /// (a + bi)(c + di) = (ac - bd) + (ad + bc)i
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, RationalError> {
+        let re = self
+            .re
+            .checked_mul(rhs.re)?
+            .checked_sub(self.im.checked_mul(rhs.im)?)?;
+        let im = self
+            .re
+            .checked_mul(rhs.im)?
+            .checked_add(self.im.checked_mul(rhs.re)?)?;
+        Ok(Self::new(re, im))
+    }
+
+    /// (a + bi)/(c + di) = (a + bi)(c - di)/(c² + d²)，除数为0时返回DivideByZero
+    pub fn checked_div(self, rhs: Self) -> Result<Self, RationalError> {
+        let norm = rhs.norm()?;
+        if norm == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        let z = self.checked_mul(rhs.conj())?;
+        Ok(Self::new(z.re.checked_div(norm)?, z.im.checked_div(norm)?))
+    }
+
+    /// 整数次幂，负指数取倒数后求幂，0的负数次幂返回DivideByZero
+    pub fn checked_pow(self, exp: i32) -> Result<Self, RationalError> {
+        let unit = Self::new(Fraction::try_from(1_u64)?, Fraction::try_from(0_u64)?);
+        let mut base = if exp < 0 {
+            unit.checked_div(self)?
+        } else {
+            self
+        };
+        let mut exp = exp.unsigned_abs();
+        let mut result = unit;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// 打印为`1/2 - 3/4i`形式，虚部为0时只打印实部，实部为0时只打印虚部
+impl<T> Display for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let im_zero = u64::from(self.im.numer()) == 0;
+        let re_zero = u64::from(self.re.numer()) == 0;
+        match (re_zero, im_zero) {
+            (_, true) => write!(f, "{}", self.re),
+            (true, false) => write!(f, "{}i", self.im),
+            (false, false) => {
+                let op = match self.im.sign() {
+                    FractionSign::NonNegative => '+',
+                    FractionSign::Negative => '-',
+                };
+                let im = Fraction {
+                    sign: FractionSign::NonNegative,
+                    ..self.im
+                };
+                write!(f, "{} {op} {im}i", self.re)
+            }
+        }
+    }
+}
+
+/// 解析`a`、`bi`、`a + bi`、`a - bi`形式，允许空白，单独的`i`视为`1i`
+impl<T> FromStr for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + FromStr + From<u8>,
+{
+    type Err = RationalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 不依赖分配器，去掉空白后写入栈上缓冲区，合法输入不会超过其长度
+        let mut buf = [0_u8; 64];
+        let mut len = 0;
+        for b in s.bytes().filter(|b| !b.is_ascii_whitespace()) {
+            *buf.get_mut(len).ok_or(RationalError::ParseFractionError)? = b;
+            len += 1;
+        }
+        // 只保留了原串中的ASCII空白以外的字节，仍是合法的UTF-8
+        let s = core::str::from_utf8(&buf[..len]).map_err(|_| RationalError::ParseFractionError)?;
+        let parse = |s: &str| match s {
+            "" => Err(RationalError::ParseFractionError),
+            s => s.parse::<Fraction<T>>(),
+        };
+        let zero = || Fraction::new(0_u8.into(), 1_u8.into(), FractionSign::NonNegative);
+        let Some(s) = s.strip_suffix('i') else {
+            return Ok(Self {
+                re: parse(s)?,
+                im: zero()?,
+            });
+        };
+        // 实部与虚部之间的符号不会出现在首位
+        let (re, im) = match s.rfind(['+', '-']) {
+            Some(k) if k > 0 => (parse(&s[..k])?, &s[k..]),
+            _ => (zero()?, s),
+        };
+        let im = match im {
+            "" | "+" => Fraction::new(1_u8.into(), 1_u8.into(), FractionSign::NonNegative)?,
+            "-" => Fraction::new(1_u8.into(), 1_u8.into(), FractionSign::Negative)?,
+            im => parse(im)?,
+        };
+        Ok(Self { re, im })
+    }
+}
+
+/// 取负
+impl<T> Neg for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+/// 加法
+impl<T> Add for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 减法
+impl<T> Sub for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 乘法
+impl<T> Mul for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 除法
+impl<T> Div for ComplexFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match self.checked_div(rhs) {
+            Ok(r) => r,
+            Err(e) => panic!("divide error: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use crate::frac::FractionU32;
+
+    use super::*;
+
+    fn c(s: &str) -> ComplexFraction<u32> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let cases = [
+            ("1/2 - 3/4i", "1/2 - 3/4i"),
+            ("1/2+3/4i", "1/2 + 3/4i"),
+            ("-2", "-2"),
+            ("-2/3i", "-2/3i"),
+            ("i", "1i"),
+            ("-i", "-1i"),
+            ("3 + i", "3 + 1i"),
+            ("-1/2 - i", "-1/2 - 1i"),
+            ("0 + 0i", "0"),
+            (" 5 /  10 - 0i ", "1/2"),
+        ];
+        for (src, expected) in cases {
+            assert_eq!(c(src).to_string(), expected, "{src}");
+            assert_eq!(c(expected), c(src));
+        }
+        for bad in ["", "i i", "1 +", "1/2 + 3/4", "1 + 2i + 3i", "1/0i", "x"] {
+            assert!(bad.parse::<ComplexFraction<u32>>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let z = c("1/2 - 3/4i");
+        let w = c("2 + i");
+        assert_eq!(z + w, c("5/2 + 1/4i"));
+        assert_eq!(z - w, c("-3/2 - 7/4i"));
+        assert_eq!(z * w, c("7/4 - i"));
+        assert_eq!(z / w, c("1/20 - 2/5i"));
+        assert_eq!(z / w * w, z);
+        assert_eq!(-z, c("-1/2 + 3/4i"));
+        assert_eq!(z.conj(), c("1/2 + 3/4i"));
+        assert_eq!(
+            z.norm(),
+            Ok(FractionU32::with_non_negative(13, 16).unwrap())
+        );
+        assert_eq!(z.re(), FractionU32::with_non_negative(1, 2).unwrap());
+        assert_eq!(z.im(), FractionU32::with_negative(3, 4).unwrap());
+        assert_eq!(z.checked_div(c("0")), Err(RationalError::DivideByZero));
+    }
+
+    #[test]
+    fn test_pow() {
+        let i = c("i");
+        let powers = ["1", "1i", "-1", "-1i"];
+        for exp in -8..=8_i32 {
+            let expected = powers[exp.rem_euclid(4) as usize];
+            assert_eq!(i.checked_pow(exp).unwrap().to_string(), expected);
+        }
+        let z = c("1 + i");
+        assert_eq!(z.checked_pow(8), Ok(c("16")));
+        assert_eq!(z.checked_pow(-2), Ok(c("-1/2i")));
+        assert_eq!(c("0").checked_pow(0), Ok(c("1")));
+        assert_eq!(c("0").checked_pow(-1), Err(RationalError::DivideByZero));
+        let z: ComplexFraction<u8> = "16 + 16i".parse().unwrap();
+        assert_eq!(z.checked_pow(2), Err(RationalError::NumeratorOverflow));
+    }
+}
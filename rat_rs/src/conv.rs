@@ -1,4 +1,4 @@
-use core::str::FromStr;
+use core::{fmt::Display, str::FromStr};
 
 use crate::{
     error::RationalError,
@@ -42,6 +42,23 @@ primitive_sign_conv!(
 primitive_sign_conv!((i16, u16, FractionU16), (i16, u32, FractionU32));
 primitive_sign_conv!((i32, u32, FractionU32));
 
+macro_rules! widening_conv {
+    ($(($narrow: ty, $wide: ty)),*) => {
+        $(
+        impl From<Fraction<$narrow>> for Fraction<$wide> {
+            fn from(value: Fraction<$narrow>) -> Self {
+                Self {
+                    numer: value.numer.into(),
+                    denom: value.denom.into(),
+                    sign: value.sign,
+                }
+            }
+        }) *
+    };
+}
+
+widening_conv!((u8, u16), (u8, u32), (u16, u32));
+
 impl<T> TryFrom<u64> for Fraction<T>
 where
     T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
@@ -144,6 +161,17 @@ where
     }
 }
 
+/// 打印为`n/d`形式，分母为1时只打印分子
+impl<T> Display for Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.write_to(f)
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -211,6 +239,25 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_display() {
+        extern crate alloc;
+        use alloc::string::ToString;
+
+        assert_eq!(FractionU32::with_non_negative(1, 2).unwrap().to_string(), "1/2");
+        assert_eq!(FractionU32::with_negative(6, 4).unwrap().to_string(), "-3/2");
+        assert_eq!(FractionU32::with_negative(3, 1).unwrap().to_string(), "-3");
+        assert_eq!(FractionU32::with_negative(0, 2).unwrap().to_string(), "0");
+        assert_eq!(
+            "-7/12"
+                .parse::<FractionU32>()
+                .unwrap()
+                .to_string()
+                .parse::<FractionU32>(),
+            FractionU32::with_negative(7, 12)
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_parse_from_str_with_panic_case() {
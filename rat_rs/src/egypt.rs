@@ -0,0 +1,281 @@
+//! egypt提供埃及分数分解，即把[0, 1)内的分数写成若干互不相同的单位分数之和
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::egypt::EgyptianStrategy;
+//! use rat_rs::frac::FractionU32;
+//!
+//! let f = FractionU32::with_non_negative(6, 7).unwrap();
+//! assert_eq!(f.egyptian().unwrap().to_string(), "1/2 + 1/3 + 1/42");
+//! assert_eq!(
+//!     f.egyptian_with(EgyptianStrategy::BinaryRemainder).unwrap().denoms(),
+//!     [2, 4, 14, 28]
+//! );
+//! ```
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt::Display;
+
+use crate::{
+    error::RationalError,
+    frac::{gcd_u128, Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 埃及分数分解策略
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum EgyptianStrategy {
+    /// 贪心算法，每次取不超过余数的最大单位分数，项数少但分母增长极快
+    Greedy,
+    /// 二进制余数法，分母不超过2q²
+    BinaryRemainder,
+    /// 拆分法，反复把重复的1/n拆成1/(n+1) + 1/(n(n+1))
+    Splitting,
+}
+
+/// 埃及分数，按分母升序保存各单位分数的分母
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Egyptian<T> {
+    denoms: Vec<T>,
+}
+
+impl<T> Egyptian<T> {
+    /// 各单位分数的分母，升序排列
+    pub fn denoms(&self) -> &[T] {
+        &self.denoms
+    }
+}
+
+/// 打印为`1/2 + 1/3 + 1/42`形式，空和打印为`0`
+impl<T> Display for Egyptian<T>
+where
+    T: Into<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.denoms.is_empty() {
+            return write!(f, "0");
+        }
+        for (i, d) in self.denoms.iter().enumerate() {
+            if i > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "1/{}", u64::from(*d))?;
+        }
+        Ok(())
+    }
+}
+
+/// 贪心算法，分母超出max或中间结果超出u128时返回DenominatorOverflow
+fn greedy(mut x: u128, mut y: u128, max: u128) -> Result<Vec<u128>, RationalError> {
+    let mut denoms = Vec::new();
+    while x != 0 {
+        let d = y.div_ceil(x);
+        if d > max {
+            return Err(RationalError::DenominatorOverflow);
+        }
+        denoms.push(d);
+        // 余数的分母y·d随项数迅速增长，d未超出max时也可能溢出u128
+        let (numer, denom) = x
+            .checked_mul(d)
+            .zip(y.checked_mul(d))
+            .ok_or(RationalError::DenominatorOverflow)?;
+        let numer = numer - y;
+        let gcd = gcd_u128(numer, denom);
+        (x, y) = (numer / gcd, denom / gcd);
+    }
+    Ok(denoms)
+}
+
+/// 二进制余数法：取N = 2^k ≥ q，p/q = a/N + r/(qN)，a与r分别按二进制位拆成单位分数
+fn binary_remainder(p: u128, q: u128) -> Vec<u128> {
+    let n = q.next_power_of_two();
+    let (a, r) = (p * n / q, p * n % q);
+    let bits = |v: u128| (0..u128::BITS).filter(move |i| v >> i & 1 == 1);
+    let mut denoms: Vec<_> = bits(a)
+        .map(|i| n >> i)
+        .chain(bits(r).map(|i| (q * n) >> i))
+        .collect();
+    denoms.sort_unstable();
+    denoms
+}
+
+/// 拆分法
+fn splitting(p: u128, q: u128, max: u128) -> Result<Vec<u128>, RationalError> {
+    let mut counts = BTreeMap::from([(q, p)]);
+    while let Some((&n, &c)) = counts.iter().find(|(_, &c)| c > 1) {
+        let next = n + 1;
+        let split = n * next;
+        if split > max {
+            return Err(RationalError::DenominatorOverflow);
+        }
+        counts.insert(n, 1);
+        *counts.entry(next).or_default() += c - 1;
+        *counts.entry(split).or_default() += c - 1;
+    }
+    Ok(counts.into_keys().collect())
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 用贪心算法分解为埃及分数，要求0 <= self < 1
+    pub fn egyptian(&self) -> Result<Egyptian<T>, RationalError> {
+        self.egyptian_with(EgyptianStrategy::Greedy)
+    }
+
+    /// 用指定策略分解为埃及分数，要求0 <= self < 1，任一分母超出T时返回DenominatorOverflow
+    pub fn egyptian_with(&self, strategy: EgyptianStrategy) -> Result<Egyptian<T>, RationalError> {
+        let (p, q) = (u64::from(self.numer()), u64::from(self.denom()));
+        if self.sign() == FractionSign::Negative || p >= q {
+            return Err(RationalError::OutOfRange);
+        }
+        if p == 0 {
+            return Ok(Egyptian { denoms: Vec::new() });
+        }
+        let (p, q) = (u128::from(p), u128::from(q));
+        let denoms = match strategy {
+            EgyptianStrategy::Greedy => greedy(p, q, T::MAX.into())?,
+            EgyptianStrategy::BinaryRemainder => binary_remainder(p, q),
+            EgyptianStrategy::Splitting => splitting(p, q, T::MAX.into())?,
+        };
+        let denoms = denoms
+            .into_iter()
+            .map(|d| {
+                u64::try_from(d)
+                    .ok()
+                    .and_then(|d| T::try_from(d).ok())
+                    .ok_or(RationalError::DenominatorOverflow)
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Egyptian { denoms })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
+
+    use super::*;
+
+    /// 校验分母严格递增且单位分数之和等于p/q
+    fn assert_decomposes(e: &Egyptian<u32>, p: u128, q: u128) {
+        assert!(e.denoms().windows(2).all(|w| w[0] < w[1]));
+        let (mut n, mut d) = (0_u128, 1_u128);
+        for &k in e.denoms() {
+            let k = u128::from(k);
+            (n, d) = (n * k + d, d * k);
+            let gcd = gcd_u128(n, d);
+            (n, d) = (n / gcd, d / gcd);
+        }
+        assert_eq!((n, d), (p, q));
+    }
+
+    #[test]
+    fn test_greedy() {
+        let f = FractionU32::with_non_negative(6, 7).unwrap();
+        assert_eq!(f.egyptian().unwrap().denoms(), [2, 3, 42]);
+        assert_eq!(f.egyptian().unwrap().to_string(), "1/2 + 1/3 + 1/42");
+        assert_eq!(
+            FractionU32::with_non_negative(4, 13)
+                .unwrap()
+                .egyptian()
+                .unwrap()
+                .denoms(),
+            [4, 18, 468]
+        );
+        assert_eq!(
+            FractionU32::from(0_u32).egyptian().unwrap().to_string(),
+            "0"
+        );
+        assert_eq!(
+            FractionU32::with_non_negative(5, 121).unwrap().egyptian(),
+            Err(RationalError::DenominatorOverflow)
+        );
+        // 前两项的分母都在u32内，余数的分母却已超出u128
+        assert_eq!(
+            FractionU32::with_non_negative(1316748153, 2714061549)
+                .unwrap()
+                .egyptian(),
+            Err(RationalError::DenominatorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        assert_eq!(
+            FractionU32::from(1_u32).egyptian(),
+            Err(RationalError::OutOfRange)
+        );
+        assert_eq!(
+            FractionU32::with_negative(1, 2).unwrap().egyptian(),
+            Err(RationalError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_binary_remainder() {
+        let e = FractionU32::with_non_negative(5, 121)
+            .unwrap()
+            .egyptian_with(EgyptianStrategy::BinaryRemainder)
+            .unwrap();
+        assert_eq!(e.denoms(), [32, 128, 484, 7744, 15488]);
+        assert_eq!(
+            FractionU16::with_non_negative(5, 121)
+                .unwrap()
+                .egyptian_with(EgyptianStrategy::BinaryRemainder)
+                .unwrap()
+                .denoms(),
+            [32, 128, 484, 7744, 15488]
+        );
+        assert_eq!(
+            FractionU8::with_non_negative(5, 121)
+                .unwrap()
+                .egyptian_with(EgyptianStrategy::BinaryRemainder),
+            Err(RationalError::DenominatorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_splitting() {
+        let e = FractionU32::with_non_negative(2, 3)
+            .unwrap()
+            .egyptian_with(EgyptianStrategy::Splitting)
+            .unwrap();
+        assert_eq!(e.denoms(), [3, 4, 12]);
+        assert_eq!(
+            FractionU8::with_non_negative(3, 7)
+                .unwrap()
+                .egyptian_with(EgyptianStrategy::Splitting),
+            Err(RationalError::DenominatorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_all_strategies_decompose_exactly() {
+        for q in 2_u32..40 {
+            for p in 1..q {
+                let f = FractionU32::with_non_negative(p, q).unwrap();
+                let (p, q) = (u128::from(f.numer()), u128::from(f.denom()));
+                for strategy in [
+                    EgyptianStrategy::Greedy,
+                    EgyptianStrategy::BinaryRemainder,
+                    EgyptianStrategy::Splitting,
+                ] {
+                    match f.egyptian_with(strategy) {
+                        Ok(e) => assert_decomposes(&e, p, q),
+                        Err(e) => assert_eq!(e, RationalError::DenominatorOverflow),
+                    }
+                }
+                assert_decomposes(
+                    &f.egyptian_with(EgyptianStrategy::BinaryRemainder).unwrap(),
+                    p,
+                    q,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! encode提供不依赖分配器的格式化输出，可以写入任意`core::fmt::Write`或定长字节缓冲区，
+//! 并为每种存储类型给出输出长度的上界，便于固件静态分配缓冲区
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//!
+//! let f = FractionU32::with_negative(2, 3).unwrap();
+//! let mut buf = [0_u8; FractionU32::MAX_RATIO_LEN];
+//! let n = f.encode_into(&mut buf).unwrap();
+//! assert_eq!(&buf[..n], b"-2/3");
+//!
+//! let mut buf = [0_u8; FractionU32::max_decimal_len(3)];
+//! let n = f.encode_decimal_into(&mut buf, 3).unwrap();
+//! assert_eq!(&buf[..n], b"-0.667");
+//! ```
+
+use core::fmt::Write;
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 写入定长字节切片的Write，空间不足时返回错误
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.len + s.len();
+        if end > self.buf.len() {
+            return Err(core::fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// `n/d`形式输出的最大字节数，包括负号
+    pub const MAX_RATIO_LEN: usize = 2 * T::MAX_DIGITS + 2;
+
+    /// 保留precision位小数时十进制形式输出的最大字节数，包括负号与小数点
+    pub const fn max_decimal_len(precision: usize) -> usize {
+        T::MAX_DIGITS + precision + 2
+    }
+
+    /// 以`n/d`形式写入w，分母为1时只写分子
+    pub fn write_to<W: Write + ?Sized>(&self, w: &mut W) -> core::fmt::Result {
+        if self.sign() == FractionSign::Negative {
+            w.write_char('-')?;
+        }
+        match u64::from(self.denom()) {
+            1 => write!(w, "{}", u64::from(self.numer())),
+            denom => write!(w, "{}/{}", u64::from(self.numer()), denom),
+        }
+    }
+
+    /// 以十进制形式写入w，保留precision位小数，四舍五入
+    pub fn write_decimal_to<W: Write + ?Sized>(
+        &self,
+        w: &mut W,
+        precision: usize,
+    ) -> core::fmt::Result {
+        let (n, d) = (u64::from(self.numer()), u64::from(self.denom()));
+        // 第一遍：找出最后一个不是9的小数位，并判断是否需要进位
+        let mut rem = n % d;
+        let mut last_non_nine = None;
+        let mut any_non_zero = false;
+        for i in 0..precision {
+            rem *= 10;
+            if rem / d != 9 {
+                last_non_nine = Some(i);
+            }
+            any_non_zero |= rem / d != 0;
+            rem %= d;
+        }
+        let round_up = rem * 2 >= d;
+        // 进位会把最后一段连续的9变成0，并使其前一位加1
+        let (int, bump) = match (round_up, last_non_nine) {
+            (false, _) => (n / d, None),
+            (true, Some(i)) => (n / d, Some(i)),
+            (true, None) => (n / d + 1, None),
+        };
+        // 舍入后全为0时不输出负号，避免出现-0.00
+        let is_zero = !round_up && int == 0 && !any_non_zero;
+        if self.sign() == FractionSign::Negative && !is_zero {
+            w.write_char('-')?;
+        }
+        write!(w, "{int}")?;
+        if precision == 0 {
+            return Ok(());
+        }
+        w.write_char('.')?;
+        // 第二遍：输出各小数位
+        let mut rem = n % d;
+        for i in 0..precision {
+            rem *= 10;
+            let digit = match bump {
+                Some(b) if i == b => rem / d + 1,
+                Some(b) if i > b => 0,
+                None if round_up => 0,
+                _ => rem / d,
+            };
+            rem %= d;
+            w.write_char(char::from(b'0' + digit as u8))?;
+        }
+        Ok(())
+    }
+
+    /// 以`n/d`形式写入buf，返回写入的字节数，buf长度不小于MAX_RATIO_LEN时一定成功
+    pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize, RationalError> {
+        let mut w = SliceWriter { buf, len: 0 };
+        self.write_to(&mut w)
+            .map_err(|_| RationalError::BufferTooSmall)?;
+        Ok(w.len)
+    }
+
+    /// 以十进制形式写入buf，返回写入的字节数，buf长度不小于max_decimal_len(precision)时一定成功
+    pub fn encode_decimal_into(
+        &self,
+        buf: &mut [u8],
+        precision: usize,
+    ) -> Result<usize, RationalError> {
+        let mut w = SliceWriter { buf, len: 0 };
+        self.write_decimal_to(&mut w, precision)
+            .map_err(|_| RationalError::BufferTooSmall)?;
+        Ok(w.len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
+
+    use super::*;
+
+    fn decimal(f: FractionU32, precision: usize) -> ([u8; 64], usize) {
+        let mut buf = [0_u8; 64];
+        let n = f.encode_decimal_into(&mut buf, precision).unwrap();
+        (buf, n)
+    }
+
+    #[test]
+    fn test_encode_ratio() {
+        let mut buf = [0_u8; FractionU32::MAX_RATIO_LEN];
+        let n = FractionU32::with_non_negative(10, 4)
+            .unwrap()
+            .encode_into(&mut buf)
+            .unwrap();
+        assert_eq!(&buf[..n], b"5/2");
+        let n = FractionU32::with_negative(7, 1)
+            .unwrap()
+            .encode_into(&mut buf)
+            .unwrap();
+        assert_eq!(&buf[..n], b"-7");
+        assert_eq!(
+            FractionU32::with_non_negative(1, 1000)
+                .unwrap()
+                .encode_into(&mut [0_u8; 5]),
+            Err(RationalError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_encode_decimal() {
+        let cases: [(FractionU32, usize, &[u8]); 12] = [
+            (FractionU32::with_non_negative(1, 3).unwrap(), 3, b"0.333"),
+            (FractionU32::with_non_negative(2, 3).unwrap(), 3, b"0.667"),
+            (FractionU32::with_negative(1, 8).unwrap(), 2, b"-0.13"),
+            (FractionU32::with_non_negative(7, 2).unwrap(), 0, b"4"),
+            (FractionU32::with_non_negative(5, 1).unwrap(), 2, b"5.00"),
+            (
+                FractionU32::with_non_negative(9999, 10000).unwrap(),
+                3,
+                b"1.000",
+            ),
+            (
+                FractionU32::with_non_negative(1999, 1000).unwrap(),
+                2,
+                b"2.00",
+            ),
+            (
+                FractionU32::with_non_negative(1295, 1000).unwrap(),
+                2,
+                b"1.30",
+            ),
+            (
+                FractionU32::with_non_negative(1, 7).unwrap(),
+                12,
+                b"0.142857142857",
+            ),
+            (FractionU32::with_negative(1, 1000).unwrap(), 2, b"0.00"),
+            (FractionU32::with_negative(1, 3).unwrap(), 0, b"0"),
+            (FractionU32::with_negative(1, 200).unwrap(), 2, b"-0.01"),
+        ];
+        for (f, precision, expected) in cases {
+            let (buf, n) = decimal(f, precision);
+            assert_eq!(&buf[..n], expected);
+        }
+    }
+
+    #[test]
+    fn test_max_len_is_sufficient() {
+        let mut buf = [0_u8; FractionU8::MAX_RATIO_LEN];
+        let f = FractionU8::with_negative(254, 255).unwrap();
+        assert_eq!(f.encode_into(&mut buf), Ok(FractionU8::MAX_RATIO_LEN));
+        let mut buf = [0_u8; FractionU16::max_decimal_len(4)];
+        let f = FractionU16::with_negative(u16::MAX, 1).unwrap();
+        assert_eq!(f.encode_decimal_into(&mut buf, 4), Ok(buf.len()));
+        let mut buf = [0_u8; FractionU32::MAX_RATIO_LEN];
+        let f = FractionU32::with_negative(u32::MAX - 1, u32::MAX).unwrap();
+        assert_eq!(f.encode_into(&mut buf), Ok(FractionU32::MAX_RATIO_LEN));
+        let mut buf = [0_u8; FractionU32::max_decimal_len(2)];
+        let f = FractionU32::with_negative(u32::MAX, 1).unwrap();
+        assert_eq!(f.encode_decimal_into(&mut buf, 2), Ok(buf.len()));
+        // 进位后整数部分位数不变
+        let mut buf = [0_u8; FractionU8::max_decimal_len(0)];
+        let f = FractionU8::with_negative(255, 2).unwrap();
+        let n = f.encode_decimal_into(&mut buf, 0).unwrap();
+        assert_eq!(&buf[..n], b"-128");
+    }
+}
@@ -7,6 +7,14 @@ pub enum RationalError {
     DenominatorOverflow,
     DivideByZero,
     ParseFractionError,
+    OutOfRange,
+    EmptyInterval,
+    UndefinedVariable,
+    BufferTooSmall,
+    Inexact,
+    EmptyInput,
+    NoSolution,
+    NestingTooDeep,
 }
 
 impl Error for RationalError {}
@@ -19,6 +27,14 @@ impl Display for RationalError {
             RationalError::DenominatorOverflow => write!(f, "denominator overflow"),
             RationalError::DivideByZero => write!(f, "divided by zero"),
             RationalError::ParseFractionError => write!(f, "parse string to fraction error"),
+            RationalError::OutOfRange => write!(f, "value out of range"),
+            RationalError::EmptyInterval => write!(f, "empty interval"),
+            RationalError::UndefinedVariable => write!(f, "undefined variable"),
+            RationalError::BufferTooSmall => write!(f, "buffer too small"),
+            RationalError::Inexact => write!(f, "inexact conversion"),
+            RationalError::EmptyInput => write!(f, "empty input"),
+            RationalError::NoSolution => write!(f, "no solution"),
+            RationalError::NestingTooDeep => write!(f, "expression nested too deeply"),
         }
     }
 }
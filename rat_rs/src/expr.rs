@@ -0,0 +1,480 @@
+//! expr提供精确分数的算术表达式求值，支持四则运算、括号、一元负号、整数次幂`^`与命名变量
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::expr::{eval, Context};
+//! use rat_rs::frac::FractionU32;
+//!
+//! assert_eq!(
+//!     eval::<u32>("(1/2 + 3/4) * 2/3 - 5").unwrap(),
+//!     FractionU32::with_negative(25, 6).unwrap()
+//! );
+//!
+//! let mut ctx = Context::<u32>::new();
+//! ctx.execute("rate = 3/8").unwrap();
+//! assert_eq!(ctx.execute("rate ^ 2").unwrap(), FractionU32::with_non_negative(9, 64).unwrap());
+//! assert_eq!(ctx.execute("1 / (rate - 3/8)").unwrap_err().span, 0..16);
+//! ```
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{error::Error, fmt::Display, ops::Range, str::FromStr};
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 表达式错误，携带出错位置在源码中的字节区间
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExprError {
+    pub error: RationalError,
+    pub span: Range<usize>,
+}
+
+impl ExprError {
+    fn new(error: RationalError, span: Range<usize>) -> Self {
+        Self { error, span }
+    }
+}
+
+impl Error for ExprError {}
+
+impl Display for ExprError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} at {}..{}",
+            self.error, self.span.start, self.span.end
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tok<'a> {
+    Num(&'a str),
+    Ident(&'a str),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    LParen,
+    RParen,
+    Assign,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'a> {
+    tok: Tok<'a>,
+    start: usize,
+    end: usize,
+}
+
+/// 词法分析
+fn tokenize(src: &str) -> Result<Vec<Token<'_>>, ExprError> {
+    let bytes = src.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let b = bytes[i];
+        let tok = match b {
+            b' ' | b'\t' | b'\r' | b'\n' => {
+                i += 1;
+                continue;
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    tok: Tok::Num(&src[start..i]),
+                    start,
+                    end: i,
+                });
+                continue;
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                tokens.push(Token {
+                    tok: Tok::Ident(&src[start..i]),
+                    start,
+                    end: i,
+                });
+                continue;
+            }
+            b'+' => Tok::Plus,
+            b'-' => Tok::Minus,
+            b'*' => Tok::Star,
+            b'/' => Tok::Slash,
+            b'^' => Tok::Caret,
+            b'(' => Tok::LParen,
+            b')' => Tok::RParen,
+            b'=' => Tok::Assign,
+            _ => {
+                let len = src[start..].chars().next().map_or(1, char::len_utf8);
+                return Err(ExprError::new(
+                    RationalError::ParseFractionError,
+                    start..start + len,
+                ));
+            }
+        };
+        i += 1;
+        tokens.push(Token { tok, start, end: i });
+    }
+    Ok(tokens)
+}
+
+/// 带区间的求值结果
+type Spanned<T> = (Fraction<T>, Range<usize>);
+
+/// 括号、一元运算符与指数的最大嵌套层数，超出时返回NestingTooDeep而不是耗尽栈空间
+const MAX_DEPTH: usize = 256;
+
+/// 递归下降求值器
+struct Parser<'a, 'c, T> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    len: usize,
+    /// 当前嵌套层数，所有递归都经过unary
+    depth: usize,
+    ctx: &'c Context<T>,
+}
+
+impl<'a, T> Parser<'a, '_, T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + FromStr + From<u8> + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    fn peek(&self) -> Option<Tok<'a>> {
+        self.tokens.get(self.pos).map(|t| t.tok)
+    }
+
+    /// 源码结尾处的空区间，用于报告表达式意外结束
+    fn eof(&self) -> Range<usize> {
+        self.len..self.len
+    }
+
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Result<Spanned<T>, ExprError> {
+        let (mut acc, mut span) = self.term()?;
+        while let Some(op @ (Tok::Plus | Tok::Minus)) = self.peek() {
+            self.pos += 1;
+            let (rhs, rspan) = self.term()?;
+            span = span.start..rspan.end;
+            let r = if op == Tok::Plus {
+                acc.checked_add(rhs)
+            } else {
+                acc.checked_sub(rhs)
+            };
+            acc = r.map_err(|e| ExprError::new(e, span.clone()))?;
+        }
+        Ok((acc, span))
+    }
+
+    /// term := unary (('*' | '/') unary)*
+    fn term(&mut self) -> Result<Spanned<T>, ExprError> {
+        let (mut acc, mut span) = self.unary()?;
+        while let Some(op @ (Tok::Star | Tok::Slash)) = self.peek() {
+            self.pos += 1;
+            let (rhs, rspan) = self.unary()?;
+            span = span.start..rspan.end;
+            let r = if op == Tok::Star {
+                acc.checked_mul(rhs)
+            } else {
+                acc.checked_div(rhs)
+            };
+            acc = r.map_err(|e| ExprError::new(e, span.clone()))?;
+        }
+        Ok((acc, span))
+    }
+
+    /// unary := ('-' | '+') unary | power
+    fn unary(&mut self) -> Result<Spanned<T>, ExprError> {
+        if self.depth == MAX_DEPTH {
+            let span = self
+                .tokens
+                .get(self.pos)
+                .map_or(self.eof(), |t| t.start..t.end);
+            return Err(ExprError::new(RationalError::NestingTooDeep, span));
+        }
+        self.depth += 1;
+        let result = match self.tokens.get(self.pos).copied() {
+            Some(Token {
+                tok: tok @ (Tok::Minus | Tok::Plus),
+                start,
+                ..
+            }) => {
+                self.pos += 1;
+                self.unary().map(|(v, span)| {
+                    let v = if tok == Tok::Minus { -v } else { v };
+                    (v, start..span.end)
+                })
+            }
+            _ => self.power(),
+        };
+        self.depth -= 1;
+        result
+    }
+
+    /// power := primary ('^' unary)?，右结合，指数必须是i32范围内的整数
+    fn power(&mut self) -> Result<Spanned<T>, ExprError> {
+        let (base, span) = self.primary()?;
+        if self.peek() != Some(Tok::Caret) {
+            return Ok((base, span));
+        }
+        self.pos += 1;
+        let (exp, espan) = self.unary()?;
+        if u64::from(exp.denom()) != 1 {
+            return Err(ExprError::new(RationalError::OutOfRange, espan));
+        }
+        let magnitude = i64::try_from(u64::from(exp.numer())).unwrap_or(i64::MAX);
+        let exp = match exp.sign() {
+            FractionSign::NonNegative => i32::try_from(magnitude),
+            FractionSign::Negative => i32::try_from(-magnitude),
+        }
+        .map_err(|_| ExprError::new(RationalError::OutOfRange, espan.clone()))?;
+        let span = span.start..espan.end;
+        let v = base
+            .checked_pow(exp)
+            .map_err(|e| ExprError::new(e, span.clone()))?;
+        Ok((v, span))
+    }
+
+    /// primary := number | ident | '(' expr ')'
+    fn primary(&mut self) -> Result<Spanned<T>, ExprError> {
+        let Some(token) = self.tokens.get(self.pos).copied() else {
+            return Err(ExprError::new(
+                RationalError::ParseFractionError,
+                self.eof(),
+            ));
+        };
+        self.pos += 1;
+        let span = token.start..token.end;
+        match token.tok {
+            Tok::Num(text) => text
+                .parse::<Fraction<T>>()
+                .map(|v| (v, span.clone()))
+                .map_err(|_| ExprError::new(RationalError::NumeratorOverflow, span)),
+            Tok::Ident(name) => self
+                .ctx
+                .get(name)
+                .map(|v| (v, span.clone()))
+                .ok_or(ExprError::new(RationalError::UndefinedVariable, span)),
+            Tok::LParen => {
+                let (v, _) = self.expr()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token {
+                        tok: Tok::RParen,
+                        end,
+                        ..
+                    }) => {
+                        self.pos += 1;
+                        Ok((v, token.start..*end))
+                    }
+                    Some(t) => Err(ExprError::new(
+                        RationalError::ParseFractionError,
+                        t.start..t.end,
+                    )),
+                    None => Err(ExprError::new(
+                        RationalError::ParseFractionError,
+                        self.eof(),
+                    )),
+                }
+            }
+            _ => Err(ExprError::new(RationalError::ParseFractionError, span)),
+        }
+    }
+}
+
+/// 求值上下文，保存命名变量
+#[derive(Debug, Clone, Default)]
+pub struct Context<T> {
+    vars: BTreeMap<String, Fraction<T>>,
+}
+
+impl<T> Context<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + FromStr + From<u8> + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    /// 创建一个没有变量的上下文
+    pub fn new() -> Self {
+        Self {
+            vars: BTreeMap::new(),
+        }
+    }
+
+    /// 设置变量，返回旧值
+    pub fn set(&mut self, name: &str, value: Fraction<T>) -> Option<Fraction<T>> {
+        self.vars.insert(String::from(name), value)
+    }
+
+    /// 获取变量
+    pub fn get(&self, name: &str) -> Option<Fraction<T>> {
+        self.vars.get(name).copied()
+    }
+
+    /// 在该上下文中对表达式求值
+    pub fn eval(&self, src: &str) -> Result<Fraction<T>, ExprError> {
+        self.eval_tokens(tokenize(src)?, src.len())
+    }
+
+    /// 执行一条语句，`name = expr`会在求值后赋值给变量，其余语句与eval相同
+    pub fn execute(&mut self, src: &str) -> Result<Fraction<T>, ExprError> {
+        let mut tokens = tokenize(src)?;
+        if let [Token {
+            tok: Tok::Ident(name),
+            ..
+        }, Token {
+            tok: Tok::Assign, ..
+        }, ..] = tokens[..]
+        {
+            let name = String::from(name);
+            let v = self.eval_tokens(tokens.split_off(2), src.len())?;
+            self.vars.insert(name, v);
+            return Ok(v);
+        }
+        self.eval_tokens(tokens, src.len())
+    }
+
+    fn eval_tokens(&self, tokens: Vec<Token<'_>>, len: usize) -> Result<Fraction<T>, ExprError> {
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            len,
+            depth: 0,
+            ctx: self,
+        };
+        let (v, _) = parser.expr()?;
+        match parser.tokens.get(parser.pos) {
+            Some(t) => Err(ExprError::new(
+                RationalError::ParseFractionError,
+                t.start..t.end,
+            )),
+            None => Ok(v),
+        }
+    }
+}
+
+/// 在空上下文中对表达式求值
+pub fn eval<T>(src: &str) -> Result<Fraction<T>, ExprError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + FromStr + From<u8> + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    Context::new().eval(src)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{frac::FractionU8, test_util::frac};
+
+    use super::*;
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(eval("(1/2 + 3/4) * 2/3 - 5"), Ok(frac(-25, 6)));
+        assert_eq!(eval("1 + 2 * 3"), Ok(frac(7, 1)));
+        assert_eq!(eval("(1 + 2) * 3"), Ok(frac(9, 1)));
+        assert_eq!(eval("12 / 4 / 3"), Ok(frac(1, 1)));
+        assert_eq!(eval("2 ^ 3 ^ 2"), Ok(frac(512, 1)));
+        assert_eq!(eval("-2 ^ 2"), Ok(frac(-4, 1)));
+        assert_eq!(eval("(-2) ^ 3"), Ok(frac(-8, 1)));
+        assert_eq!(eval("2 ^ -2"), Ok(frac(1, 4)));
+        assert_eq!(eval("--3"), Ok(frac(3, 1)));
+        assert_eq!(eval("(2/3) ^ (1 + 1)"), Ok(frac(4, 9)));
+    }
+
+    #[test]
+    fn test_variables() {
+        let mut ctx = Context::new();
+        assert_eq!(ctx.execute("x = 1/3"), Ok(frac(1, 3)));
+        assert_eq!(ctx.execute("y = x * 3"), Ok(frac(1, 1)));
+        assert_eq!(ctx.eval("x + y"), Ok(frac(4, 3)));
+        ctx.set("x", frac(2, 1));
+        assert_eq!(ctx.eval("x ^ 10"), Ok(frac(1024, 1)));
+        assert_eq!(
+            ctx.eval("x + z"),
+            Err(ExprError::new(RationalError::UndefinedVariable, 4..5))
+        );
+        assert_eq!(ctx.get("z"), None);
+    }
+
+    #[test]
+    fn test_error_spans() {
+        assert_eq!(
+            eval::<u32>("1 + 2 / (3 - 3)"),
+            Err(ExprError::new(RationalError::DivideByZero, 4..15))
+        );
+        assert_eq!(
+            eval::<u8>("100 * 3"),
+            Err(ExprError::new(RationalError::NumeratorOverflow, 0..7))
+        );
+        assert_eq!(
+            eval::<u8>("1 + 256"),
+            Err(ExprError::new(RationalError::NumeratorOverflow, 4..7))
+        );
+        assert_eq!(
+            eval::<u32>("2 ^ (1/2)"),
+            Err(ExprError::new(RationalError::OutOfRange, 4..9))
+        );
+        assert_eq!(
+            eval::<u32>("0 ^ -1"),
+            Err(ExprError::new(RationalError::DivideByZero, 0..6))
+        );
+        assert_eq!(
+            eval::<u32>("(1 + 2"),
+            Err(ExprError::new(RationalError::ParseFractionError, 6..6))
+        );
+        assert_eq!(
+            eval::<u32>("1 + * 2"),
+            Err(ExprError::new(RationalError::ParseFractionError, 4..5))
+        );
+        assert_eq!(
+            eval::<u32>("1 2"),
+            Err(ExprError::new(RationalError::ParseFractionError, 2..3))
+        );
+        assert_eq!(
+            eval::<u32>("1 % 2"),
+            Err(ExprError::new(RationalError::ParseFractionError, 2..3))
+        );
+        assert_eq!(
+            eval::<u32>(""),
+            Err(ExprError::new(RationalError::ParseFractionError, 0..0))
+        );
+        assert_eq!(
+            eval::<u8>("(1/2) ^ 2").map(|v| v == FractionU8::with_non_negative(1, 4).unwrap()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_nesting_limit() {
+        let nested = |open: &str, n: usize, close: &str| {
+            let mut s = open.repeat(n);
+            s.push('1');
+            s.push_str(&close.repeat(n));
+            s
+        };
+        assert_eq!(eval(&nested("(", MAX_DEPTH - 1, ")")), Ok(frac(1, 1)));
+        assert_eq!(eval(&nested("-", MAX_DEPTH - 1, "")), Ok(frac(-1, 1)));
+        assert_eq!(
+            eval::<u32>(&nested("(", MAX_DEPTH, ")")),
+            Err(ExprError::new(RationalError::NestingTooDeep, 256..257))
+        );
+        assert_eq!(
+            eval::<u32>(&nested("-", 100_000, "")),
+            Err(ExprError::new(RationalError::NestingTooDeep, 256..257))
+        );
+        assert_eq!(
+            eval::<u32>(&nested("1^", 100_000, "")).map_err(|e| e.error),
+            Err(RationalError::NestingTooDeep)
+        );
+    }
+}
@@ -0,0 +1,302 @@
+//! Farey序列与Stern–Brocot树
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::farey::{mediant, Farey, SternBrocotMove};
+//! use rat_rs::frac::FractionU32;
+//!
+//! let f3: Vec<_> = Farey::<u32>::new(3).unwrap().map(|f| (f.numer(), f.denom())).collect();
+//! assert_eq!(f3, [(0, 1), (1, 3), (1, 2), (2, 3), (1, 1)]);
+//!
+//! let a = FractionU32::with_non_negative(1, 2).unwrap();
+//! let b = FractionU32::with_non_negative(2, 3).unwrap();
+//! assert_eq!(mediant(a, b).unwrap(), FractionU32::with_non_negative(3, 5).unwrap());
+//!
+//! let path: Vec<_> = FractionU32::with_non_negative(3, 5).unwrap().stern_brocot_path().unwrap().collect();
+//! assert_eq!(path, [SternBrocotMove::Left, SternBrocotMove::Right, SternBrocotMove::Left]);
+//! ```
+
+use core::fmt::Display;
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 求两个分数的中位分数 (a.numer + b.numer) / (a.denom + b.denom)，分子按符号相加
+pub fn mediant<T>(a: Fraction<T>, b: Fraction<T>) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    Wide {
+        n: a.signed_numer() + b.signed_numer(),
+        d: i128::from(u64::from(a.denom())) + i128::from(u64::from(b.denom())),
+    }
+    .to_fraction()
+}
+
+/// n阶Farey序列迭代器，按升序产生[0, 1]内所有分母不超过n的最简分数
+pub struct Farey<T> {
+    n: u64,
+    /// 当前项 a/b
+    a: u64,
+    b: u64,
+    /// 下一项 c/d
+    c: u64,
+    d: u64,
+    done: bool,
+    _marker: core::marker::PhantomData<T>,
+}
+
+impl<T> Farey<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 创建n阶Farey序列，n必须为正数
+    pub fn new(n: T) -> Result<Self, RationalError> {
+        let n = u64::from(n);
+        if n == 0 {
+            return Err(RationalError::OutOfRange);
+        }
+        Ok(Self {
+            n,
+            a: 0,
+            b: 1,
+            c: 1,
+            d: n,
+            done: false,
+            _marker: core::marker::PhantomData,
+        })
+    }
+}
+
+impl<T> Iterator for Farey<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    type Item = Fraction<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (a, b) = (self.a, self.b);
+        if a == b {
+            self.done = true;
+        } else {
+            let k = (self.n + self.b) / self.d;
+            let (c, d) = (k * self.c - self.a, k * self.d - self.b);
+            self.a = core::mem::replace(&mut self.c, c);
+            self.b = core::mem::replace(&mut self.d, d);
+        }
+        // 分子分母均不超过n，一定能转换为T
+        let (Ok(numer), Ok(denom)) = (T::try_from(a), T::try_from(b)) else {
+            unreachable!("farey terms never exceed n")
+        };
+        Some(Fraction::new(numer, denom, FractionSign::NonNegative).unwrap())
+    }
+}
+
+/// Stern–Brocot树上的一步移动
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SternBrocotMove {
+    /// 走向左子树(更小)
+    Left,
+    /// 走向右子树(更大)
+    Right,
+}
+
+impl Display for SternBrocotMove {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SternBrocotMove::Left => write!(f, "L"),
+            SternBrocotMove::Right => write!(f, "R"),
+        }
+    }
+}
+
+/// 从树根1/1走到某个正分数的路径迭代器
+pub struct SternBrocotPath {
+    m: u64,
+    n: u64,
+}
+
+impl Iterator for SternBrocotPath {
+    type Item = SternBrocotMove;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.m < self.n {
+            self.n -= self.m;
+            Some(SternBrocotMove::Left)
+        } else if self.m > self.n {
+            self.m -= self.n;
+            Some(SternBrocotMove::Right)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 获取从树根1/1到该分数的Stern–Brocot路径，只有正分数位于树上
+    pub fn stern_brocot_path(&self) -> Result<SternBrocotPath, RationalError> {
+        if self.sign() == FractionSign::Negative || u64::from(self.numer()) == 0 {
+            return Err(RationalError::OutOfRange);
+        }
+        Ok(SternBrocotPath {
+            m: u64::from(self.numer()),
+            n: u64::from(self.denom()),
+        })
+    }
+
+    /// 沿Stern–Brocot路径从树根1/1出发构造分数，分子或分母一超出T即返回错误，不再消耗剩余路径
+    pub fn from_stern_brocot_path<I>(path: I) -> Result<Self, RationalError>
+    where
+        I: IntoIterator<Item = SternBrocotMove>,
+    {
+        // 左边界 a/b 与右边界 c/d，当前节点为二者的中位分数
+        let (mut a, mut b, mut c, mut d) = (0_u64, 1_u64, 1_u64, 0_u64);
+        for step in path {
+            let (m, n) = (a + c, b + d);
+            match step {
+                SternBrocotMove::Left => (c, d) = (m, n),
+                SternBrocotMove::Right => (a, b) = (m, n),
+            }
+            // 各边界都不超过T::MAX，相加不会溢出u64
+            if a + c > T::MAX {
+                return Err(RationalError::NumeratorOverflow);
+            }
+            if b + d > T::MAX {
+                return Err(RationalError::DenominatorOverflow);
+            }
+        }
+        let numer = T::try_from(a + c).map_err(|_| RationalError::NumeratorOverflow)?;
+        let denom = T::try_from(b + d).map_err(|_| RationalError::DenominatorOverflow)?;
+        Self::new(numer, denom, FractionSign::NonNegative)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
+
+    use super::*;
+
+    #[test]
+    fn test_farey_sequence() {
+        let f5: [(u32, u32); 11] = [
+            (0, 1),
+            (1, 5),
+            (1, 4),
+            (1, 3),
+            (2, 5),
+            (1, 2),
+            (3, 5),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (1, 1),
+        ];
+        assert!(Farey::<u32>::new(5)
+            .unwrap()
+            .zip(f5)
+            .all(|(f, (n, d))| f == FractionU32::with_non_negative(n, d).unwrap()));
+        assert_eq!(Farey::<u32>::new(5).unwrap().count(), 11);
+        assert_eq!(Farey::<u32>::new(1).unwrap().count(), 2);
+        assert_eq!(
+            Farey::<u8>::new(255).unwrap().last(),
+            Some(FractionU8::from(1_u8))
+        );
+        assert!(Farey::<u32>::new(0).is_err());
+    }
+
+    #[test]
+    fn test_mediant() {
+        assert_eq!(
+            mediant(FractionU32::from(0_u32), FractionU32::from(1_u32)),
+            FractionU32::with_non_negative(1, 2)
+        );
+        assert_eq!(
+            mediant(
+                FractionU32::with_negative(1, 2).unwrap(),
+                FractionU32::with_non_negative(1, 3).unwrap()
+            ),
+            FractionU32::with_non_negative(0, 5)
+        );
+        assert_eq!(
+            mediant(
+                FractionU8::with_non_negative(200, 1).unwrap(),
+                FractionU8::with_non_negative(100, 1).unwrap()
+            ),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_stern_brocot_path_round_trip() {
+        use SternBrocotMove::*;
+
+        let f = FractionU32::with_non_negative(5, 7).unwrap();
+        let path: [SternBrocotMove; 4] = [Left, Right, Right, Left];
+        assert!(f.stern_brocot_path().unwrap().eq(path));
+        assert_eq!(FractionU32::from_stern_brocot_path(path), Ok(f));
+        assert_eq!(
+            FractionU32::from(1_u32)
+                .stern_brocot_path()
+                .unwrap()
+                .count(),
+            0
+        );
+        assert_eq!(
+            FractionU32::from_stern_brocot_path([]),
+            Ok(FractionU32::from(1_u32))
+        );
+        assert!(FractionU32::from(0_u32).stern_brocot_path().is_err());
+        assert!(FractionU32::with_negative(1, 2)
+            .unwrap()
+            .stern_brocot_path()
+            .is_err());
+        for f in Farey::<u32>::new(12).unwrap().skip(1) {
+            assert_eq!(
+                FractionU32::from_stern_brocot_path(f.stern_brocot_path().unwrap()),
+                Ok(f)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_stern_brocot_path_with_overflow() {
+        assert_eq!(
+            FractionU8::from_stern_brocot_path([SternBrocotMove::Right; 255]),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU8::from_stern_brocot_path([SternBrocotMove::Left; 255]),
+            Err(RationalError::DenominatorOverflow)
+        );
+        // 超出T后立即返回，无限长的路径也能结束
+        assert_eq!(
+            FractionU16::from_stern_brocot_path(core::iter::repeat(SternBrocotMove::Right)),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU8::from_stern_brocot_path(
+                [SternBrocotMove::Left, SternBrocotMove::Right]
+                    .into_iter()
+                    .cycle()
+            ),
+            Err(RationalError::DenominatorOverflow)
+        );
+        assert_eq!(
+            FractionU8::from_stern_brocot_path([SternBrocotMove::Right; 254]),
+            Ok(FractionU8::from(255_u8))
+        );
+    }
+}
@@ -0,0 +1,353 @@
+//! fixed提供分母固定的分数FixedFraction，分母D是类型的一部分，
+//! 加减法只需对分子做整数运算，不必求最大公约数，适合时间基固定的热循环，例如1/48000的音频采样刻度
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::fixed::FixedFraction;
+//! use rat_rs::frac::{FractionSign, FractionU32};
+//! use rat_rs::round::RoundingMode;
+//!
+//! type Tick = FixedFraction<u32, 48000>;
+//!
+//! let a = Tick::new(24000, FractionSign::NonNegative);
+//! let b = Tick::new(12000, FractionSign::Negative);
+//! assert_eq!((a + b).numer(), 12000);
+//! assert_eq!(
+//!     FractionU32::try_from(a + b).unwrap(),
+//!     FractionU32::with_non_negative(1, 4).unwrap()
+//! );
+//! assert_eq!(a.checked_mul(a, RoundingMode::HalfEven).unwrap().numer(), 12000);
+//! ```
+
+use core::{
+    cmp::Ordering,
+    ops::{Add, Neg, Sub},
+};
+
+use crate::{
+    error::RationalError,
+    frac::{gcd, Fraction, FractionSign, UnsignedFractionInt},
+    round::{div_round, RoundingMode},
+    wide::split_signed,
+};
+
+/// 分母固定为D的分数，值为±numer/D，分子不做约分
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FixedFraction<T, const D: u64> {
+    numer: T,
+    sign: FractionSign,
+}
+
+impl<T, const D: u64> FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 分母
+    pub const DENOM: u64 = D;
+
+    const NONZERO_DENOM: () = assert!(D != 0, "zero as denominator");
+
+    /// 构造±numer/D
+    pub fn new(numer: T, sign: FractionSign) -> Self {
+        let () = Self::NONZERO_DENOM;
+        // 零没有符号，统一视为非负
+        let sign = if u64::from(numer) == 0 {
+            FractionSign::NonNegative
+        } else {
+            sign
+        };
+        Self { numer, sign }
+    }
+
+    /// 零
+    pub fn zero() -> Self {
+        Self::new(T::try_from(0).ok().unwrap(), FractionSign::NonNegative)
+    }
+
+    /// 分子
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    /// 负号
+    pub fn sign(&self) -> FractionSign {
+        self.sign
+    }
+
+    /// 带符号的分子
+    fn signed(self) -> i128 {
+        let n = i128::from(u64::from(self.numer));
+        match self.sign {
+            FractionSign::NonNegative => n,
+            FractionSign::Negative => -n,
+        }
+    }
+
+    /// 由带符号的分子构造，绝对值超出T时返回NumeratorOverflow
+    fn from_signed(n: i128) -> Result<Self, RationalError> {
+        let (numer, sign) = split_signed(n)?;
+        Ok(Self::new(numer, sign))
+    }
+
+    /// 由分子绝对值与符号构造，绝对值超出T时返回NumeratorOverflow
+    fn from_magnitude(n: u128, sign: FractionSign) -> Result<Self, RationalError> {
+        let numer = u64::try_from(n)
+            .ok()
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or(RationalError::NumeratorOverflow)?;
+        Ok(Self::new(numer, sign))
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, RationalError> {
+        Self::from_signed(self.signed() + rhs.signed())
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, RationalError> {
+        Self::from_signed(self.signed() - rhs.signed())
+    }
+
+    /// 乘法，乘积a·b/D²按mode舍入到最近的n/D
+    pub fn checked_mul(self, rhs: Self, mode: RoundingMode) -> Result<Self, RationalError> {
+        let sign = if self.sign == rhs.sign {
+            FractionSign::NonNegative
+        } else {
+            FractionSign::Negative
+        };
+        let n = u128::from(u64::from(self.numer)) * u128::from(u64::from(rhs.numer));
+        let n = div_round(n, u128::from(D), sign == FractionSign::Negative, mode);
+        Self::from_magnitude(n, sign)
+    }
+
+    /// 除法，商a/b按mode舍入到最近的n/D
+    pub fn checked_div(self, rhs: Self, mode: RoundingMode) -> Result<Self, RationalError> {
+        if u64::from(rhs.numer) == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        let sign = if self.sign == rhs.sign {
+            FractionSign::NonNegative
+        } else {
+            FractionSign::Negative
+        };
+        let n = u128::from(u64::from(self.numer)) * u128::from(D);
+        let n = div_round(
+            n,
+            u128::from(u64::from(rhs.numer)),
+            sign == FractionSign::Negative,
+            mode,
+        );
+        Self::from_magnitude(n, sign)
+    }
+
+    /// 把分数按mode舍入到最近的n/D
+    pub fn from_fraction(value: Fraction<T>, mode: RoundingMode) -> Result<Self, RationalError> {
+        let n = u128::from(u64::from(value.numer())) * u128::from(D);
+        let n = div_round(
+            n,
+            u128::from(u64::from(value.denom())),
+            value.sign() == FractionSign::Negative,
+            mode,
+        );
+        Self::from_magnitude(n, value.sign())
+    }
+}
+
+/// 约分后转换为分数，约分后的分母超出T时返回DenominatorOverflow
+impl<T, const D: u64> TryFrom<FixedFraction<T, D>> for Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Error = RationalError;
+
+    fn try_from(value: FixedFraction<T, D>) -> Result<Self, Self::Error> {
+        let numer = u64::from(value.numer);
+        let gcd = gcd(numer, D);
+        let denom = T::try_from(D / gcd).map_err(|_| RationalError::DenominatorOverflow)?;
+        let numer = T::try_from(numer / gcd).map_err(|_| RationalError::NumeratorOverflow)?;
+        Fraction::new(numer, denom, value.sign)
+    }
+}
+
+/// 无损转换为分母为D的分数，分母不整除D时返回Inexact
+impl<T, const D: u64> TryFrom<Fraction<T>> for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Error = RationalError;
+
+    fn try_from(value: Fraction<T>) -> Result<Self, Self::Error> {
+        let denom = u64::from(value.denom());
+        if !D.is_multiple_of(denom) {
+            return Err(RationalError::Inexact);
+        }
+        let n = u128::from(u64::from(value.numer())) * u128::from(D / denom);
+        Self::from_magnitude(n, value.sign())
+    }
+}
+
+/// 加法
+impl<T, const D: u64> Add for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("numerator overflow")
+    }
+}
+
+/// 减法
+impl<T, const D: u64> Sub for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("numerator overflow")
+    }
+}
+
+/// 负号
+impl<T, const D: u64> Neg for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(self.numer, -self.sign)
+    }
+}
+
+/// 全序比较，分母相同，只需比较带符号的分子
+impl<T, const D: u64> Ord for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.signed().cmp(&other.signed())
+    }
+}
+
+/// 偏序比较
+impl<T, const D: u64> PartialOrd for FixedFraction<T, D>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
+
+    use super::*;
+
+    type Tick = FixedFraction<u32, 48000>;
+
+    fn tick(n: i64) -> Tick {
+        Tick::from_signed(i128::from(n)).unwrap()
+    }
+
+    #[test]
+    fn test_add_sub() {
+        assert_eq!(tick(3) + tick(-5), tick(-2));
+        assert_eq!(tick(-3) - tick(-3), Tick::zero());
+        assert_eq!((tick(-3) - tick(-3)).sign(), FractionSign::NonNegative);
+        assert_eq!(-tick(7), tick(-7));
+        assert_eq!(
+            FixedFraction::<u8, 10>::new(200, FractionSign::NonNegative)
+                .checked_add(FixedFraction::new(56, FractionSign::NonNegative)),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert!(tick(-1) < tick(0) && tick(0) < tick(1) && tick(-2) < tick(-1));
+    }
+
+    #[test]
+    fn test_mul_div_rounding() {
+        type Tenth = FixedFraction<u32, 10>;
+        let t = |n| Tenth::from_signed(n).unwrap();
+        // 0.5 * 0.5 = 0.25
+        let cases = [
+            (RoundingMode::Down, 2, -2),
+            (RoundingMode::Up, 3, -3),
+            (RoundingMode::Floor, 2, -3),
+            (RoundingMode::Ceiling, 3, -2),
+            (RoundingMode::HalfUp, 3, -3),
+            (RoundingMode::HalfDown, 2, -2),
+            (RoundingMode::HalfEven, 2, -2),
+        ];
+        for (mode, pos, neg) in cases {
+            assert_eq!(t(5).checked_mul(t(5), mode), Ok(t(pos)));
+            assert_eq!(t(5).checked_mul(t(-5), mode), Ok(t(neg)));
+        }
+        // 0.7 * 0.5 = 0.35
+        assert_eq!(t(7).checked_mul(t(5), RoundingMode::HalfEven), Ok(t(4)));
+        // 1 / 3 = 0.333..
+        assert_eq!(t(10).checked_div(t(30), RoundingMode::HalfUp), Ok(t(3)));
+        assert_eq!(t(-10).checked_div(t(30), RoundingMode::Floor), Ok(t(-4)));
+        assert_eq!(
+            t(1).checked_div(t(0), RoundingMode::Down),
+            Err(RationalError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_fraction_conversion() {
+        let f = FractionU32::with_negative(3, 8).unwrap();
+        let fixed = Tick::try_from(f).unwrap();
+        assert_eq!(fixed, tick(-18000));
+        assert_eq!(FractionU32::try_from(fixed), Ok(f));
+        assert_eq!(
+            Tick::try_from(FractionU32::with_non_negative(1, 7).unwrap()),
+            Err(RationalError::Inexact)
+        );
+        assert_eq!(
+            Tick::from_fraction(
+                FractionU32::with_non_negative(1, 7).unwrap(),
+                RoundingMode::HalfEven
+            ),
+            Ok(tick(6857))
+        );
+        // 约分后分母仍超出u8
+        assert_eq!(
+            FractionU8::try_from(FixedFraction::<u8, 48000>::new(
+                1,
+                FractionSign::NonNegative
+            )),
+            Err(RationalError::DenominatorOverflow)
+        );
+        // 约分后可以放入u8
+        assert_eq!(
+            FractionU8::try_from(FixedFraction::<u8, 48000>::new(
+                250,
+                FractionSign::NonNegative
+            )),
+            Ok(FractionU8::with_non_negative(1, 192).unwrap())
+        );
+        assert_eq!(
+            FixedFraction::<u16, 4>::try_from(FractionU16::from(20000_u16)),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for n in -100..=100 {
+            let fixed = FixedFraction::<u32, 360>::from_signed(n).unwrap();
+            let f = FractionU32::try_from(fixed).unwrap();
+            assert_eq!(FixedFraction::try_from(f), Ok(fixed));
+        }
+    }
+}
@@ -78,11 +78,25 @@ where
 
 impl<T> Eq for Fraction<T> where T: Eq {}
 
-pub trait UnsignedFractionInt: Copy {}
+pub trait UnsignedFractionInt: Copy {
+    /// 该类型能表示的最大值
+    const MAX: u64;
+    /// 最大值的十进制位数
+    const MAX_DIGITS: usize;
+}
 
-impl UnsignedFractionInt for u8 {}
-impl UnsignedFractionInt for u16 {}
-impl UnsignedFractionInt for u32 {}
+impl UnsignedFractionInt for u8 {
+    const MAX: u64 = u8::MAX as u64;
+    const MAX_DIGITS: usize = 3;
+}
+impl UnsignedFractionInt for u16 {
+    const MAX: u64 = u16::MAX as u64;
+    const MAX_DIGITS: usize = 5;
+}
+impl UnsignedFractionInt for u32 {
+    const MAX: u64 = u32::MAX as u64;
+    const MAX_DIGITS: usize = 10;
+}
 
 impl<T> Fraction<T>
 where
@@ -92,6 +106,12 @@ where
         if denom.into() == 0 {
             return Err(RationalError::ZeroDenominator);
         }
+        // 零没有符号，统一视为非负，保证相等比较与全序正确
+        let sign = if numer.into() == 0 {
+            FractionSign::NonNegative
+        } else {
+            sign
+        };
         let gcd = gcd(numer.into(), denom.into());
         let numer =
             T::try_from(numer.into() / gcd).map_err(|_| RationalError::NumeratorOverflow)?;
@@ -122,6 +142,18 @@ where
     pub fn sign(&self) -> FractionSign {
         self.sign
     }
+
+    /// 带符号的分子
+    pub(crate) fn signed_numer(&self) -> i128
+    where
+        u64: From<T>,
+    {
+        let numer = i128::from(u64::from(self.numer));
+        match self.sign {
+            FractionSign::NonNegative => numer,
+            FractionSign::Negative => -numer,
+        }
+    }
 }
 
 /// 求两个u64的最大公约数
@@ -133,6 +165,15 @@ pub(crate) fn gcd(mut m: u64, mut n: u64) -> u64 {
     m
 }
 
+/// 求两个u128的最大公约数
+pub(crate) fn gcd_u128(mut m: u128, mut n: u128) -> u128 {
+    while n != 0 {
+        let remainder = m % n;
+        m = core::mem::replace(&mut n, remainder);
+    }
+    m
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -148,6 +189,18 @@ mod test {
         assert_eq!(gcd(1, 42), 1);
     }
 
+    #[test]
+    fn test_zero_is_non_negative() {
+        assert_eq!(
+            FractionU32::with_negative(0, 3),
+            FractionU32::with_non_negative(0, 1)
+        );
+        assert_eq!(
+            -FractionU32::with_non_negative(0, 1).unwrap(),
+            FractionU32::with_non_negative(0, 1).unwrap()
+        );
+    }
+
     #[test]
     fn test_new_fraction_with_corner_cases() {
         assert_eq!(
@@ -0,0 +1,445 @@
+//! interval提供端点为分数的精确区间运算，端点可开可闭，用于误差与不确定度的传播
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::interval::Interval;
+//!
+//! let half = FractionU32::with_non_negative(1, 2).unwrap();
+//! let one = FractionU32::from(1_u32);
+//! let a = Interval::closed(half, one).unwrap();
+//! let b = Interval::open(one, one + one).unwrap();
+//! let sum = a + b;
+//! assert_eq!(sum, Interval::open(one + half, one + one + one).unwrap());
+//! assert!(sum.contains(&(one + one)));
+//! ```
+
+use core::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 区间，保证非空且下端点不大于上端点
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Interval<F> {
+    lo: F,
+    hi: F,
+    lo_open: bool,
+    hi_open: bool,
+}
+
+impl<T> Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    /// 构造区间，端点开闭由参数指定，区间为空时返回EmptyInterval
+    fn with_bounds(
+        lo: Fraction<T>,
+        lo_open: bool,
+        hi: Fraction<T>,
+        hi_open: bool,
+    ) -> Result<Self, RationalError> {
+        match lo.cmp(&hi) {
+            Ordering::Greater => Err(RationalError::EmptyInterval),
+            Ordering::Equal if lo_open || hi_open => Err(RationalError::EmptyInterval),
+            _ => Ok(Self {
+                lo,
+                hi,
+                lo_open,
+                hi_open,
+            }),
+        }
+    }
+
+    /// 闭区间[lo, hi]
+    pub fn closed(lo: Fraction<T>, hi: Fraction<T>) -> Result<Self, RationalError> {
+        Self::with_bounds(lo, false, hi, false)
+    }
+
+    /// 开区间(lo, hi)
+    pub fn open(lo: Fraction<T>, hi: Fraction<T>) -> Result<Self, RationalError> {
+        Self::with_bounds(lo, true, hi, true)
+    }
+
+    /// 左闭右开区间[lo, hi)
+    pub fn closed_open(lo: Fraction<T>, hi: Fraction<T>) -> Result<Self, RationalError> {
+        Self::with_bounds(lo, false, hi, true)
+    }
+
+    /// 左开右闭区间(lo, hi]
+    pub fn open_closed(lo: Fraction<T>, hi: Fraction<T>) -> Result<Self, RationalError> {
+        Self::with_bounds(lo, true, hi, false)
+    }
+
+    /// 只包含一个点的区间[x, x]
+    pub fn point(x: Fraction<T>) -> Self {
+        Self {
+            lo: x,
+            hi: x,
+            lo_open: false,
+            hi_open: false,
+        }
+    }
+
+    /// 下端点
+    pub fn lo(&self) -> Fraction<T> {
+        self.lo
+    }
+
+    /// 上端点
+    pub fn hi(&self) -> Fraction<T> {
+        self.hi
+    }
+
+    /// 下端点是否为开
+    pub fn is_lo_open(&self) -> bool {
+        self.lo_open
+    }
+
+    /// 上端点是否为开
+    pub fn is_hi_open(&self) -> bool {
+        self.hi_open
+    }
+
+    /// 区间宽度hi - lo
+    pub fn width(&self) -> Result<Fraction<T>, RationalError> {
+        self.hi.checked_sub(self.lo)
+    }
+
+    /// 判断x是否在区间内
+    pub fn contains(&self, x: &Fraction<T>) -> bool {
+        let above_lo = match x.cmp(&self.lo) {
+            Ordering::Greater => true,
+            Ordering::Equal => !self.lo_open,
+            Ordering::Less => false,
+        };
+        let below_hi = match x.cmp(&self.hi) {
+            Ordering::Less => true,
+            Ordering::Equal => !self.hi_open,
+            Ordering::Greater => false,
+        };
+        above_lo && below_hi
+    }
+
+    /// 判断other是否是该区间的子集
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        let lo_ok = match other.lo.cmp(&self.lo) {
+            Ordering::Greater => true,
+            Ordering::Equal => !self.lo_open || other.lo_open,
+            Ordering::Less => false,
+        };
+        let hi_ok = match other.hi.cmp(&self.hi) {
+            Ordering::Less => true,
+            Ordering::Equal => !self.hi_open || other.hi_open,
+            Ordering::Greater => false,
+        };
+        lo_ok && hi_ok
+    }
+
+    /// 交集，不相交时返回None
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (lo, lo_open) = match self.lo.cmp(&other.lo) {
+            Ordering::Greater => (self.lo, self.lo_open),
+            Ordering::Less => (other.lo, other.lo_open),
+            Ordering::Equal => (self.lo, self.lo_open || other.lo_open),
+        };
+        let (hi, hi_open) = match self.hi.cmp(&other.hi) {
+            Ordering::Less => (self.hi, self.hi_open),
+            Ordering::Greater => (other.hi, other.hi_open),
+            Ordering::Equal => (self.hi, self.hi_open && other.hi_open),
+        };
+        Self::with_bounds(lo, lo_open, hi, hi_open).ok()
+    }
+
+    /// 包含两个区间的最小区间
+    pub fn hull(&self, other: &Self) -> Self {
+        let (lo, lo_open) = match self.lo.cmp(&other.lo) {
+            Ordering::Less => (self.lo, self.lo_open),
+            Ordering::Greater => (other.lo, other.lo_open),
+            Ordering::Equal => (self.lo, self.lo_open && other.lo_open),
+        };
+        let (hi, hi_open) = match self.hi.cmp(&other.hi) {
+            Ordering::Greater => (self.hi, self.hi_open),
+            Ordering::Less => (other.hi, other.hi_open),
+            Ordering::Equal => (self.hi, self.hi_open && other.hi_open),
+        };
+        Self {
+            lo,
+            hi,
+            lo_open,
+            hi_open,
+        }
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, RationalError> {
+        Ok(Self {
+            lo: self.lo.checked_add(rhs.lo)?,
+            hi: self.hi.checked_add(rhs.hi)?,
+            lo_open: self.lo_open || rhs.lo_open,
+            hi_open: self.hi_open || rhs.hi_open,
+        })
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, RationalError> {
+        self.checked_add(-rhs)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, RationalError> {
+        let is_zero = |f: &Fraction<T>| u64::from(f.numer()) == 0;
+        // 端点乘积能否取到：两个端点都闭合，或者闭合的一端为0
+        let candidate = |(x, x_open): (Fraction<T>, bool), (y, y_open): (Fraction<T>, bool)| {
+            let attained = (!x_open && (!y_open || is_zero(&x))) || (!y_open && is_zero(&y));
+            x.checked_mul(y).map(|v| (v, !attained))
+        };
+        let (l, r) = ((self.lo, self.lo_open), (self.hi, self.hi_open));
+        let candidates = [
+            candidate(l, (rhs.lo, rhs.lo_open))?,
+            candidate(l, (rhs.hi, rhs.hi_open))?,
+            candidate(r, (rhs.lo, rhs.lo_open))?,
+            candidate(r, (rhs.hi, rhs.hi_open))?,
+        ];
+        let (mut lo, mut lo_open) = candidates[0];
+        let (mut hi, mut hi_open) = candidates[0];
+        for (v, open) in candidates.into_iter().skip(1) {
+            match v.cmp(&lo) {
+                Ordering::Less => (lo, lo_open) = (v, open),
+                Ordering::Equal => lo_open &= open,
+                Ordering::Greater => {}
+            }
+            match v.cmp(&hi) {
+                Ordering::Greater => (hi, hi_open) = (v, open),
+                Ordering::Equal => hi_open &= open,
+                Ordering::Less => {}
+            }
+        }
+        Ok(Self {
+            lo,
+            hi,
+            lo_open,
+            hi_open,
+        })
+    }
+
+    /// 除法，除数区间的闭包包含0时结果无界，返回DivideByZero
+    pub fn checked_div(self, rhs: Self) -> Result<Self, RationalError> {
+        let lo_non_positive =
+            rhs.lo.sign() == FractionSign::Negative || u64::from(rhs.lo.numer()) == 0;
+        if lo_non_positive && rhs.hi.sign() == FractionSign::NonNegative {
+            return Err(RationalError::DivideByZero);
+        }
+        let recip = |f: Fraction<T>| Fraction::new(f.denom(), f.numer(), f.sign());
+        self.checked_mul(Self {
+            lo: recip(rhs.hi)?,
+            hi: recip(rhs.lo)?,
+            lo_open: rhs.hi_open,
+            hi_open: rhs.lo_open,
+        })
+    }
+}
+
+/// 打印为`[1/2, 1)`形式
+impl<T> Display for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lo_open { '(' } else { '[' },
+            self.lo,
+            self.hi,
+            if self.hi_open { ')' } else { ']' }
+        )
+    }
+}
+
+/// 取负
+impl<T> Neg for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+            lo_open: self.hi_open,
+            hi_open: self.lo_open,
+        }
+    }
+}
+
+/// 加法
+impl<T> Add for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 减法
+impl<T> Sub for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 乘法
+impl<T> Mul for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 除法
+impl<T> Div for Interval<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match self.checked_div(rhs) {
+            Ok(r) => r,
+            Err(e) => panic!("divide error: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+    use std::string::ToString;
+
+    use crate::{frac::FractionU8, test_util::frac};
+
+    use super::*;
+
+    #[test]
+    fn test_construct_and_contains() {
+        assert_eq!(
+            Interval::closed(frac(1, 1), frac(1, 2)),
+            Err(RationalError::EmptyInterval)
+        );
+        assert_eq!(
+            Interval::closed_open(frac(1, 2), frac(1, 2)),
+            Err(RationalError::EmptyInterval)
+        );
+        let i = Interval::closed_open(frac(-1, 2), frac(3, 4)).unwrap();
+        assert!(i.contains(&frac(-1, 2)));
+        assert!(i.contains(&frac(0, 1)));
+        assert!(!i.contains(&frac(3, 4)));
+        assert!(!i.contains(&frac(-1, 1)));
+        assert_eq!(i.width(), Ok(frac(5, 4)));
+        assert_eq!(i.to_string(), "[-1/2, 3/4)");
+        assert!(Interval::point(frac(2, 3)).contains(&frac(4, 6)));
+    }
+
+    #[test]
+    fn test_intersection_and_hull() {
+        let a = Interval::closed(frac(0, 1), frac(1, 1)).unwrap();
+        let b = Interval::open(frac(1, 2), frac(2, 1)).unwrap();
+        assert_eq!(
+            a.intersection(&b),
+            Some(Interval::open_closed(frac(1, 2), frac(1, 1)).unwrap())
+        );
+        assert_eq!(
+            a.hull(&b),
+            Interval::closed_open(frac(0, 1), frac(2, 1)).unwrap()
+        );
+        let c = Interval::open(frac(1, 1), frac(2, 1)).unwrap();
+        assert_eq!(a.intersection(&c), None);
+        assert!(a.hull(&b).contains_interval(&a));
+        assert!(a.hull(&b).contains_interval(&b));
+        assert!(!a.contains_interval(&b));
+        assert!(Interval::closed(frac(1, 2), frac(1, 1))
+            .unwrap()
+            .contains_interval(&Interval::open(frac(1, 2), frac(1, 1)).unwrap()));
+        assert!(!Interval::open(frac(1, 2), frac(1, 1))
+            .unwrap()
+            .contains_interval(&Interval::closed(frac(1, 2), frac(1, 1)).unwrap()));
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let a = Interval::closed(frac(1, 2), frac(1, 1)).unwrap();
+        let b = Interval::closed_open(frac(-1, 3), frac(1, 3)).unwrap();
+        assert_eq!(
+            a + b,
+            Interval::closed_open(frac(1, 6), frac(4, 3)).unwrap()
+        );
+        assert_eq!(
+            a - b,
+            Interval::open_closed(frac(1, 6), frac(4, 3)).unwrap()
+        );
+        assert_eq!(
+            Interval::point(FractionU8::with_non_negative(200, 1).unwrap()).checked_add(
+                Interval::point(FractionU8::with_non_negative(100, 1).unwrap())
+            ),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = Interval::closed_open(frac(0, 1), frac(1, 1)).unwrap();
+        let b = Interval::open_closed(frac(-1, 1), frac(2, 1)).unwrap();
+        assert_eq!(a * b, Interval::open(frac(-1, 1), frac(2, 1)).unwrap());
+        let c = Interval::closed(frac(-2, 1), frac(3, 1)).unwrap();
+        let d = Interval::closed(frac(-1, 2), frac(1, 4)).unwrap();
+        assert_eq!(c * d, Interval::closed(frac(-3, 2), frac(1, 1)).unwrap());
+        let e = Interval::open(frac(0, 1), frac(1, 1)).unwrap();
+        assert_eq!(e * e, e);
+        assert_eq!(Interval::point(frac(0, 1)) * e, Interval::point(frac(0, 1)));
+    }
+
+    #[test]
+    fn test_div() {
+        let a = Interval::closed(frac(1, 1), frac(2, 1)).unwrap();
+        let b = Interval::closed_open(frac(2, 1), frac(4, 1)).unwrap();
+        assert_eq!(
+            a / b,
+            Interval::open_closed(frac(1, 4), frac(1, 1)).unwrap()
+        );
+        let neg = Interval::closed(frac(-4, 1), frac(-2, 1)).unwrap();
+        assert_eq!(a / neg, Interval::closed(frac(-1, 1), frac(-1, 4)).unwrap());
+        for divisor in [
+            Interval::closed(frac(-1, 1), frac(1, 1)).unwrap(),
+            Interval::open(frac(0, 1), frac(1, 1)).unwrap(),
+            Interval::closed(frac(-1, 1), frac(0, 1)).unwrap(),
+        ] {
+            assert_eq!(a.checked_div(divisor), Err(RationalError::DivideByZero));
+        }
+    }
+}
@@ -0,0 +1,371 @@
+//! lazy提供延迟约分的分数LazyFraction，分子分母以u64保存，运算时不求最大公约数，
+//! 只在输出、转换为`Fraction<T>`或结果将要超出u64时约分。相等与全序比较通过u128交叉相乘完成，
+//! 不需要先约分。适合大量累加后才读取结果的场景
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::lazy::LazyFraction;
+//!
+//! let mut sum = LazyFraction::from(FractionU32::from(0_u32));
+//! for k in 1..=6_u32 {
+//!     sum += FractionU32::with_non_negative(1, k * (k + 1)).unwrap().into();
+//! }
+//! assert_eq!(sum.to_string(), "6/7");
+//! assert_eq!(FractionU32::try_from(sum), Ok(FractionU32::with_non_negative(6, 7).unwrap()));
+//! ```
+
+use core::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::RationalError,
+    frac::{gcd, gcd_u128, Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 未约分的分数±numer/denom，分母恒为正
+#[derive(Debug, Clone, Copy)]
+pub struct LazyFraction {
+    numer: u64,
+    denom: u64,
+    sign: FractionSign,
+}
+
+impl LazyFraction {
+    /// 构造±numer/denom，不约分
+    pub fn new(numer: u64, denom: u64, sign: FractionSign) -> Result<Self, RationalError> {
+        if denom == 0 {
+            return Err(RationalError::ZeroDenominator);
+        }
+        Ok(Self::unchecked(numer, denom, sign))
+    }
+
+    fn unchecked(numer: u64, denom: u64, sign: FractionSign) -> Self {
+        // 零没有符号，统一视为非负
+        let sign = if numer == 0 {
+            FractionSign::NonNegative
+        } else {
+            sign
+        };
+        Self { numer, denom, sign }
+    }
+
+    /// 由u128分子分母构造，超出u64时先约分，约分后仍超出则返回溢出错误
+    fn from_wide(numer: u128, denom: u128, sign: FractionSign) -> Result<Self, RationalError> {
+        if let (Ok(n), Ok(d)) = (u64::try_from(numer), u64::try_from(denom)) {
+            return Ok(Self::unchecked(n, d, sign));
+        }
+        let gcd = gcd_u128(numer, denom);
+        let numer = u64::try_from(numer / gcd).map_err(|_| RationalError::NumeratorOverflow)?;
+        let denom = u64::try_from(denom / gcd).map_err(|_| RationalError::DenominatorOverflow)?;
+        Ok(Self::unchecked(numer, denom, sign))
+    }
+
+    /// 当前保存的分子，未必约分
+    pub fn numer(&self) -> u64 {
+        self.numer
+    }
+
+    /// 当前保存的分母，未必约分
+    pub fn denom(&self) -> u64 {
+        self.denom
+    }
+
+    /// 负号
+    pub fn sign(&self) -> FractionSign {
+        self.sign
+    }
+
+    /// 约分后的值
+    pub fn normalized(self) -> Self {
+        let gcd = gcd(self.numer, self.denom);
+        Self::unchecked(self.numer / gcd, self.denom / gcd, self.sign)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, RationalError> {
+        match self.add_wide(rhs) {
+            Some(r) => r,
+            // 交叉相乘后的和超出u128，约分后重试一次
+            None => self
+                .normalized()
+                .add_wide(rhs.normalized())
+                .unwrap_or(Err(RationalError::NumeratorOverflow)),
+        }
+    }
+
+    /// 在u128中相加，分母相同或一方整除另一方时不做乘法，和超出u128时返回None
+    fn add_wide(self, rhs: Self) -> Option<Result<Self, RationalError>> {
+        let (n1, d1) = (u128::from(self.numer), u128::from(self.denom));
+        let (n2, d2) = (u128::from(rhs.numer), u128::from(rhs.denom));
+        let (l, r, d) = if d1 == d2 {
+            (n1, n2, d1)
+        } else if d1 % d2 == 0 {
+            (n1, n2 * (d1 / d2), d1)
+        } else if d2 % d1 == 0 {
+            (n1 * (d2 / d1), n2, d2)
+        } else {
+            (n1 * d2, n2 * d1, d1 * d2)
+        };
+        let (n, sign) = if self.sign == rhs.sign {
+            (l.checked_add(r)?, self.sign)
+        } else if l >= r {
+            (l - r, self.sign)
+        } else {
+            (r - l, rhs.sign)
+        };
+        Some(Self::from_wide(n, d, sign))
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, RationalError> {
+        self.checked_add(-rhs)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, RationalError> {
+        let sign = FractionSign::from(self.sign as u8 ^ rhs.sign as u8);
+        Self::from_wide(
+            u128::from(self.numer) * u128::from(rhs.numer),
+            u128::from(self.denom) * u128::from(rhs.denom),
+            sign,
+        )
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, RationalError> {
+        if rhs.numer == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        self.checked_mul(Self::unchecked(rhs.denom, rhs.numer, rhs.sign))
+    }
+}
+
+impl<T> From<Fraction<T>> for LazyFraction
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn from(value: Fraction<T>) -> Self {
+        Self::unchecked(value.numer().into(), value.denom().into(), value.sign())
+    }
+}
+
+/// 约分后转换为分数，超出T时返回溢出错误
+impl<T> TryFrom<LazyFraction> for Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Error = RationalError;
+
+    fn try_from(value: LazyFraction) -> Result<Self, Self::Error> {
+        let value = value.normalized();
+        let numer = T::try_from(value.numer).map_err(|_| RationalError::NumeratorOverflow)?;
+        let denom = T::try_from(value.denom).map_err(|_| RationalError::DenominatorOverflow)?;
+        Fraction::new(numer, denom, value.sign)
+    }
+}
+
+/// 约分后打印为`n/d`形式，分母为1时只打印分子
+impl Display for LazyFraction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let value = self.normalized();
+        if value.sign == FractionSign::Negative {
+            write!(f, "-")?;
+        }
+        match value.denom {
+            1 => write!(f, "{}", value.numer),
+            denom => write!(f, "{}/{}", value.numer, denom),
+        }
+    }
+}
+
+/// 交叉相乘判断相等，无需约分
+impl PartialEq for LazyFraction {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for LazyFraction {}
+
+/// 全序比较，在u128中交叉相乘
+impl Ord for LazyFraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let l = u128::from(self.numer) * u128::from(other.denom);
+        let r = u128::from(other.numer) * u128::from(self.denom);
+        match (self.sign, other.sign) {
+            (FractionSign::NonNegative, FractionSign::NonNegative) => l.cmp(&r),
+            (FractionSign::Negative, FractionSign::Negative) => r.cmp(&l),
+            (FractionSign::NonNegative, FractionSign::Negative) => Ordering::Greater,
+            (FractionSign::Negative, FractionSign::NonNegative) => Ordering::Less,
+        }
+    }
+}
+
+/// 偏序比较
+impl PartialOrd for LazyFraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 负号
+impl Neg for LazyFraction {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::unchecked(self.numer, self.denom, -self.sign)
+    }
+}
+
+/// 加法
+impl Add for LazyFraction {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 累加
+impl AddAssign for LazyFraction {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+/// 减法
+impl Sub for LazyFraction {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 乘法
+impl Mul for LazyFraction {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 除法
+impl Div for LazyFraction {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        match self.checked_div(rhs) {
+            Ok(r) => r,
+            Err(e) => panic!("divide error: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::string::ToString;
+
+    use crate::frac::{FractionU32, FractionU8};
+
+    use super::*;
+
+    fn lazy(n: u64, d: u64) -> LazyFraction {
+        LazyFraction::new(n, d, FractionSign::NonNegative).unwrap()
+    }
+
+    #[test]
+    fn test_skips_reduction() {
+        let a = lazy(2, 4) + lazy(1, 4);
+        assert_eq!((a.numer(), a.denom()), (3, 4));
+        let b = lazy(1, 6) + lazy(1, 3);
+        assert_eq!((b.numer(), b.denom()), (3, 6));
+        let c = lazy(2, 3) * lazy(3, 4);
+        assert_eq!((c.numer(), c.denom()), (6, 12));
+        assert_eq!(c, lazy(1, 2));
+        assert_eq!(c.normalized().numer(), 1);
+        assert_eq!(c.to_string(), "1/2");
+        assert_eq!(
+            LazyFraction::new(1, 0, FractionSign::Negative).unwrap_err(),
+            RationalError::ZeroDenominator
+        );
+    }
+
+    #[test]
+    fn test_eq_and_ord() {
+        assert_eq!(lazy(3, 9), lazy(100, 300));
+        assert_eq!(-lazy(0, 5), lazy(0, 7));
+        assert!(lazy(1, 3) < lazy(34, 100));
+        assert!(-lazy(1, 3) > -lazy(34, 100));
+        assert!(-lazy(u64::MAX, 1) < lazy(0, 1));
+        // 交叉相乘在u128中进行，不会溢出
+        assert!(lazy(u64::MAX, u64::MAX - 1) < lazy(u64::MAX - 1, u64::MAX - 2));
+    }
+
+    #[test]
+    fn test_matches_eager() {
+        for (n1, d1) in [(1_u32, 2_u32), (5, 12), (7, 30), (0, 1), (99, 100)] {
+            for (n2, d2) in [(1_u32, 3_u32), (11, 12), (3, 8), (4, 1)] {
+                for negative in [false, true] {
+                    let a = FractionU32::with_non_negative(n1, d1).unwrap();
+                    let b = FractionU32::with_non_negative(n2, d2).unwrap();
+                    let b = if negative { -b } else { b };
+                    let (la, lb) = (LazyFraction::from(a), LazyFraction::from(b));
+                    assert_eq!(FractionU32::try_from(la + lb), Ok(a + b));
+                    assert_eq!(FractionU32::try_from(la - lb), Ok(a - b));
+                    assert_eq!(FractionU32::try_from(la * lb), Ok(a * b));
+                    assert_eq!(FractionU32::try_from(la / lb), Ok(a / b));
+                    assert_eq!(la.cmp(&lb), a.cmp(&b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduces_near_overflow() {
+        // 分母的乘积超出u64，约分后仍能放下
+        let a = lazy(1 << 40, 1 << 41);
+        let b = lazy(1 << 39, 1 << 41);
+        let c = a * b;
+        assert_eq!((c.numer(), c.denom()), (1, 8));
+        // 累乘(k + 1)/k，未约分的分子分母很快超出u64，约分后为61
+        let mut prod = lazy(1, 1);
+        for k in 1..=60 {
+            prod = prod * lazy(k + 1, k);
+        }
+        assert_eq!(prod, lazy(61, 1));
+        let mut sum = lazy(0, 1);
+        for k in 1..=40_u64 {
+            sum += lazy(1, 1 << (k % 20)) * lazy(3, 7);
+        }
+        assert_eq!(sum, lazy(3 * ((1 << 20) - 1), 7 << 18));
+        assert_eq!(
+            lazy(u64::MAX, 1).checked_add(lazy(u64::MAX, 1)),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            lazy(1, u64::MAX).checked_mul(lazy(1, u64::MAX - 1)),
+            Err(RationalError::DenominatorOverflow)
+        );
+        assert_eq!(
+            lazy(1, 2).checked_div(lazy(0, 3)),
+            Err(RationalError::DivideByZero)
+        );
+        assert_eq!(
+            FractionU8::try_from(lazy(300, 600)),
+            Ok(FractionU8::with_non_negative(1, 2).unwrap())
+        );
+        assert_eq!(
+            FractionU8::try_from(lazy(300, 1)),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+}
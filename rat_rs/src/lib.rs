@@ -1,6 +1,33 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub mod approx;
+pub mod batch;
+pub mod complex;
 pub mod conv;
+#[cfg(feature = "alloc")]
+pub mod egypt;
+pub mod encode;
 pub mod error;
+#[cfg(feature = "alloc")]
+pub mod expr;
+pub mod farey;
+pub mod fixed;
 pub mod frac;
+pub mod interval;
+pub mod lazy;
+pub mod modular;
 pub mod ops;
+#[cfg(feature = "alloc")]
+pub mod poly;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod round;
+pub mod series;
+#[cfg(feature = "alloc")]
+pub mod stats;
+#[cfg(test)]
+mod test_util;
+mod wide;
@@ -0,0 +1,245 @@
+//! modular提供有理数重构：由模m的剩余r找出分子分母都不超过界的分数n/d，使n ≡ r·d (mod m)，
+//! 以及把多个模数下的剩余合并为一个剩余的中国剩余定理，用于在素数模下做精确线性代数
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::modular::crt;
+//!
+//! // -3/7 在两个素数下的剩余
+//! let (p1, p2) = (4294967291_u128, 4294967279_u128);
+//! let (r1, r2) = (2454267023_u128, 1840700262_u128);
+//! let (r, m) = crt(&[(r1, p1), (r2, p2)]).unwrap();
+//! assert_eq!(m, p1 * p2);
+//! assert_eq!(FractionU32::reconstruct(r, m), Ok(FractionU32::with_negative(3, 7).unwrap()));
+//! ```
+
+use crate::{
+    error::RationalError,
+    frac::{gcd_u128, Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 模m加法，要求a, b < m
+fn addmod(a: u128, b: u128, m: u128) -> u128 {
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// 模m乘法，乘积超出u128时用倍加法，要求a, b < m
+fn mulmod(mut a: u128, mut b: u128, m: u128) -> u128 {
+    if let Some(p) = a.checked_mul(b) {
+        return p % m;
+    }
+    let mut r = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            r = addmod(r, a, m);
+        }
+        a = addmod(a, a, m);
+        b >>= 1;
+    }
+    r
+}
+
+/// a在模m下的逆元，不存在时返回None。
+/// 扩展欧几里得算法中a的系数正负交替，只记录绝对值，因此不会溢出
+fn inverse(a: u128, m: u128) -> Option<u128> {
+    let (mut r0, mut r1) = (m, a % m);
+    let (mut s0, mut s1) = (0_u128, 1_u128);
+    let mut negative = true;
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (s0, s1) = (s1, s0 + q * s1);
+        negative = !negative;
+    }
+    if r0 != 1 {
+        return None;
+    }
+    let s = s0 % m;
+    Some(if negative && s != 0 { m - s } else { s })
+}
+
+/// 中国剩余定理：合并若干(剩余, 模数)，返回模为各模数最小公倍数的剩余。
+/// 模数不必两两互素，剩余互相矛盾时返回NoSolution，模数为0或最小公倍数超出u128时返回OutOfRange
+pub fn crt(residues: &[(u128, u128)]) -> Result<(u128, u128), RationalError> {
+    residues
+        .iter()
+        .try_fold((0_u128, 1_u128), |(r1, m1), &(r2, m2)| {
+            if m2 == 0 {
+                return Err(RationalError::OutOfRange);
+            }
+            let r2 = r2 % m2;
+            let g = gcd_u128(m1, m2);
+            if r1 % g != r2 % g {
+                return Err(RationalError::NoSolution);
+            }
+            let (m1g, m2g) = (m1 / g, m2 / g);
+            let lcm = m1.checked_mul(m2g).ok_or(RationalError::OutOfRange)?;
+            if m2g == 1 {
+                return Ok((r1, lcm));
+            }
+            // 求k使m1·k ≡ r2 - r1 (mod m2)，即(m1/g)·k ≡ (r2 - r1)/g (mod m2/g)
+            let diff = if r2 >= r1 {
+                ((r2 - r1) / g) % m2g
+            } else {
+                (m2g - ((r1 - r2) / g) % m2g) % m2g
+            };
+            let inv = inverse(m1g % m2g, m2g).ok_or(RationalError::NoSolution)?;
+            let k = mulmod(diff, inv, m2g);
+            // r1 < m1且k < m2/g，结果小于lcm，不会溢出
+            Ok((r1 + m1 * k, lcm))
+        })
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 有理数重构：求n/d使n ≡ residue·d (mod modulus)，|n|与d都不超过min(T::MAX, ⌊√((m - 1)/2)⌋)，
+    /// 在此界下解若存在则唯一。不存在时返回NoSolution，modulus小于2时返回OutOfRange
+    pub fn reconstruct(residue: u128, modulus: u128) -> Result<Self, RationalError> {
+        if modulus < 2 {
+            return Err(RationalError::OutOfRange);
+        }
+        let bound = u128::from(T::MAX).min(((modulus - 1) / 2).isqrt());
+        // 半扩展欧几里得：余数降到界以下即停止，t的符号正负交替，只记录绝对值
+        let (mut r0, mut r1) = (modulus, residue % modulus);
+        let (mut t0, mut t1) = (0_u128, 1_u128);
+        let mut negative = false;
+        while r1 > bound {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 + q * t1);
+            negative = !negative;
+        }
+        if t1 > bound || gcd_u128(r1, t1) != 1 {
+            return Err(RationalError::NoSolution);
+        }
+        // 两者都不超过T::MAX，转换不会失败
+        let (Ok(numer), Ok(denom)) = (T::try_from(r1 as u64), T::try_from(t1 as u64)) else {
+            unreachable!("reconstructed parts never exceed T::MAX")
+        };
+        let sign = if negative {
+            FractionSign::Negative
+        } else {
+            FractionSign::NonNegative
+        };
+        Self::new(numer, denom, sign)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
+
+    use super::*;
+
+    const P1: u128 = 4294967291;
+    const P2: u128 = 4294967279;
+    const P3: u128 = 4294967231;
+
+    /// ±n/d在模m下的剩余
+    fn residue(n: u128, d: u128, negative: bool, m: u128) -> u128 {
+        let r = mulmod(n % m, inverse(d, m).unwrap(), m);
+        if negative && r != 0 {
+            m - r
+        } else {
+            r
+        }
+    }
+
+    #[test]
+    fn test_inverse_and_mulmod() {
+        assert_eq!(inverse(3, 7), Some(5));
+        assert_eq!(inverse(4, 8), None);
+        let m = u128::MAX - 158; // 2^128 - 159是素数
+        let a = m - 2;
+        assert_eq!(mulmod(a, a, m), 4);
+        assert_eq!(mulmod(a, inverse(a, m).unwrap(), m), 1);
+    }
+
+    #[test]
+    fn test_reconstruct() {
+        for (n, d) in [
+            (0_u32, 1_u32),
+            (1, 2),
+            (3, 7),
+            (65535, 65521),
+            (1, 65535),
+            (40000, 3),
+        ] {
+            for negative in [false, true] {
+                let r = residue(n.into(), d.into(), negative, P1 * P2);
+                let f = FractionU32::new(
+                    n,
+                    d,
+                    if negative {
+                        FractionSign::Negative
+                    } else {
+                        FractionSign::NonNegative
+                    },
+                )
+                .unwrap();
+                assert_eq!(FractionU32::reconstruct(r, P1 * P2), Ok(f));
+            }
+        }
+        // 模数太小时界缩小为70，1/1000重构为界内与之同余的-10/7，71则在界内无解
+        assert_eq!(
+            FractionU32::reconstruct(residue(1, 1000, false, 10007), 10007),
+            Ok(FractionU32::with_negative(10, 7).unwrap())
+        );
+        assert_eq!(
+            FractionU32::reconstruct(71, 10007),
+            Err(RationalError::NoSolution)
+        );
+        assert_eq!(
+            FractionU32::reconstruct(residue(1, 70, false, 10007), 10007),
+            Ok(FractionU32::with_non_negative(1, 70).unwrap())
+        );
+        // 分子超出u8
+        assert_eq!(
+            FractionU8::reconstruct(residue(300, 7, false, P1), P1),
+            Err(RationalError::NoSolution)
+        );
+        assert_eq!(
+            FractionU8::reconstruct(residue(250, 7, true, P1), P1),
+            Ok(FractionU8::with_negative(250, 7).unwrap())
+        );
+        assert_eq!(
+            FractionU16::reconstruct(5, 1),
+            Err(RationalError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[]), Ok((0, 1)));
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        // 模数不互素但相容
+        assert_eq!(crt(&[(3, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt(&[(3, 4), (4, 6)]), Err(RationalError::NoSolution));
+        assert_eq!(crt(&[(1, 0)]), Err(RationalError::OutOfRange));
+        assert_eq!(
+            crt(&[(1, P1 * P2), (1, P3 * P3 * P3)]).unwrap_err(),
+            RationalError::OutOfRange
+        );
+        // 单个素数的模不足以唯一确定u32分数，合并三个后可以重构
+        let (n, d) = (4000000007_u128, 3999999999_u128);
+        let residues = [P1, P2, P3].map(|p| (residue(n, d, true, p), p));
+        let (r, m) = crt(&residues).unwrap();
+        assert_eq!(m, P1 * P2 * P3);
+        assert_eq!(
+            FractionU32::reconstruct(r, m),
+            Ok(FractionU32::with_negative(4000000007, 3999999999).unwrap())
+        );
+        assert_ne!(
+            FractionU32::reconstruct(residues[0].0, P1),
+            FractionU32::reconstruct(r, m)
+        );
+    }
+}
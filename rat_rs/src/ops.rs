@@ -115,10 +115,11 @@ where
                 let numer_part1 = u64::from(self.numer()) * u64::from(rhs.denom());
                 let numer_part2 = u64::from(self.denom()) * u64::from(rhs.numer());
                 let denom = u64::from(self.denom()) * u64::from(rhs.denom());
+                // 结果的符号与绝对值较大的一方相同
                 let (numer, sign) = if numer_part1 >= numer_part2 {
-                    (numer_part1 - numer_part2, FractionSign::NonNegative)
+                    (numer_part1 - numer_part2, self.sign())
                 } else {
-                    (numer_part2 - numer_part1, FractionSign::Negative)
+                    (numer_part2 - numer_part1, rhs.sign())
                 };
                 let gcd = gcd(numer, denom);
                 let numer = T::try_from(numer / gcd).map_err(|_| RationalError::NumeratorOverflow)?;
@@ -157,9 +158,104 @@ where
         if rhs == 0 {
             return Err(RationalError::DivideByZero);
         }
-        let rhs = Self::new(rhs.denom(), self.numer(), self.sign())?;
+        let rhs = Self::new(rhs.denom(), rhs.numer(), rhs.sign())?;
         self.checked_mul(rhs)
     }
+
+    /// 整数次幂，负指数取倒数后求幂，0的负数次幂返回DivideByZero
+    pub fn checked_pow(self, exp: i32) -> Result<Self, RationalError>
+    where
+        u64: From<T>,
+    {
+        let mut base = if exp < 0 {
+            if u64::from(self.numer()) == 0 {
+                return Err(RationalError::DivideByZero);
+            }
+            Self::new(self.denom(), self.numer(), self.sign())?
+        } else {
+            self
+        };
+        let mut exp = exp.unsigned_abs();
+        let mut result = Self::new(self.denom(), self.denom(), FractionSign::NonNegative)?;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// 与i64整数的运算，在i128中计算，不构造分母为1的临时分数，
+/// 整数本身可以超出T，只要结果能放入T
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 由已经互素的带符号分子与分母构造，不再约分
+    fn from_coprime(numer: i128, denom: u128) -> Result<Self, RationalError> {
+        let sign = if numer < 0 {
+            FractionSign::Negative
+        } else {
+            FractionSign::NonNegative
+        };
+        let numer = u64::try_from(numer.unsigned_abs())
+            .ok()
+            .and_then(|n| T::try_from(n).ok())
+            .ok_or(RationalError::NumeratorOverflow)?;
+        let denom = u64::try_from(denom)
+            .ok()
+            .and_then(|d| T::try_from(d).ok())
+            .ok_or(RationalError::DenominatorOverflow)?;
+        Ok(Self { numer, denom, sign })
+    }
+
+    /// 加整数，gcd(a + kd, d) = gcd(a, d) = 1，无需约分
+    pub fn checked_add_int(self, rhs: i64) -> Result<Self, RationalError> {
+        let denom = u64::from(self.denom());
+        Self::from_coprime(
+            self.signed_numer() + i128::from(rhs) * i128::from(denom),
+            denom.into(),
+        )
+    }
+
+    /// 减整数，无需约分
+    pub fn checked_sub_int(self, rhs: i64) -> Result<Self, RationalError> {
+        let denom = u64::from(self.denom());
+        Self::from_coprime(
+            self.signed_numer() - i128::from(rhs) * i128::from(denom),
+            denom.into(),
+        )
+    }
+
+    /// 乘整数，只需约去整数与分母的最大公约数
+    pub fn checked_mul_int(self, rhs: i64) -> Result<Self, RationalError> {
+        let denom = u64::from(self.denom());
+        let gcd = gcd(rhs.unsigned_abs(), denom);
+        Self::from_coprime(
+            self.signed_numer() * (i128::from(rhs) / i128::from(gcd)),
+            u128::from(denom / gcd),
+        )
+    }
+
+    /// 除以整数，只需约去整数与分子的最大公约数，除数为0时返回DivideByZero
+    pub fn checked_div_int(self, rhs: i64) -> Result<Self, RationalError> {
+        if rhs == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        let numer = self.signed_numer();
+        let gcd = i128::from(gcd(numer.unsigned_abs() as u64, rhs.unsigned_abs()));
+        let (numer, rhs) = (numer / gcd, i128::from(rhs) / gcd);
+        Self::from_coprime(
+            if rhs < 0 { -numer } else { numer },
+            u128::from(u64::from(self.denom())) * rhs.unsigned_abs(),
+        )
+    }
 }
 
 /// 全序比较
@@ -193,10 +289,90 @@ where
     }
 }
 
+/// 两种存储宽度中较宽的一种，用于不同宽度分数之间的比较与运算
+pub trait Promote<U> {
+    type Output: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq;
+}
+
+macro_rules! promote {
+    ($(($a: ty, $b: ty) => $out: ty),*) => {
+        $(
+        impl Promote<$b> for $a {
+            type Output = $out;
+        }) *
+    };
+}
+
+promote!(
+    (u8, u8) => u8, (u8, u16) => u16, (u8, u32) => u32,
+    (u16, u8) => u16, (u16, u16) => u16, (u16, u32) => u32,
+    (u32, u8) => u32, (u32, u16) => u32, (u32, u32) => u32
+);
+
+macro_rules! promoted_arith {
+    ($($(#[$doc: meta])* $method: ident => $checked: ident),*) => {
+        $(
+        $(#[$doc])*
+        pub fn $method<U>(self, rhs: Fraction<U>) -> Result<Fraction<T::Output>, RationalError>
+        where
+            T: Promote<U>,
+            u64: From<T::Output>,
+            u32: From<T::Output>,
+            Fraction<T::Output>: From<Self> + From<Fraction<U>>,
+        {
+            Fraction::<T::Output>::from(self).$checked(Fraction::from(rhs))
+        }
+        ) *
+    };
+}
+
+/// 不同存储宽度之间的比较与运算，两侧先无损扩展为较宽的类型再计算
+///
+/// 这里有意只提供方法而不实现`PartialEq<Fraction<U>>`、`Add<Fraction<U>>`等运算符：
+/// 同一类型上有多个这样的实现时，`f + Fraction::with_negative(1, 2).unwrap()`就无法再推断右侧的T
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    /// 与另一存储宽度的分数判等
+    pub fn eq_promoted<U>(&self, other: &Fraction<U>) -> bool
+    where
+        T: Promote<U>,
+        U: Copy,
+        Fraction<T::Output>: From<Self> + From<Fraction<U>>,
+    {
+        Fraction::<T::Output>::from(*self) == Fraction::from(*other)
+    }
+
+    /// 与另一存储宽度的分数比较大小
+    pub fn cmp_promoted<U>(&self, other: &Fraction<U>) -> core::cmp::Ordering
+    where
+        T: Promote<U>,
+        U: Copy,
+        u64: From<T::Output>,
+        Fraction<T::Output>: From<Self> + From<Fraction<U>>,
+    {
+        Fraction::<T::Output>::from(*self).cmp(&Fraction::from(*other))
+    }
+
+    promoted_arith!(
+        /// 加法，结果为较宽的类型
+        checked_add_promoted => checked_add,
+        /// 减法，结果为较宽的类型
+        checked_sub_promoted => checked_sub,
+        /// 乘法，结果为较宽的类型
+        checked_mul_promoted => checked_mul,
+        /// 除法，结果为较宽的类型，除数为0时返回DivideByZero
+        checked_div_promoted => checked_div
+    );
+}
+
 #[cfg(test)]
 mod test {
 
-    use crate::frac::FractionU32;
+    use core::cmp::Ordering;
+
+    use crate::frac::{FractionU16, FractionU32, FractionU8};
 
     use super::*;
 
@@ -231,6 +407,14 @@ mod test {
             FractionU32::with_non_negative(1, 3).unwrap() + Fraction::with_negative(1, 2).unwrap(),
             FractionU32::new(1, 6, FractionSign::Negative).unwrap()
         );
+        assert_eq!(
+            FractionU32::with_negative(1, 2).unwrap() + Fraction::with_non_negative(1, 3).unwrap(),
+            FractionU32::new(1, 6, FractionSign::Negative).unwrap()
+        );
+        assert_eq!(
+            FractionU32::with_negative(1, 3).unwrap() + Fraction::with_non_negative(1, 2).unwrap(),
+            FractionU32::new(1, 6, FractionSign::NonNegative).unwrap()
+        );
         assert_eq!(
             FractionU32::with_non_negative(1, 2).unwrap() + 1.into(),
             Fraction::with_non_negative(3, 2).unwrap()
@@ -244,6 +428,49 @@ mod test {
         let _ = f + f;
     }
 
+    #[test]
+    fn test_fraction_div() {
+        assert_eq!(
+            FractionU32::with_non_negative(2, 3).unwrap()
+                / FractionU32::with_non_negative(1, 5).unwrap(),
+            FractionU32::with_non_negative(10, 3).unwrap()
+        );
+        assert_eq!(
+            FractionU32::with_non_negative(1, 2).unwrap() / FractionU32::with_negative(3, 4).unwrap(),
+            FractionU32::with_negative(2, 3).unwrap()
+        );
+        assert_eq!(
+            FractionU32::with_negative(1, 2).unwrap() / FractionU32::with_negative(1, 4).unwrap(),
+            FractionU32::with_non_negative(2, 1).unwrap()
+        );
+        assert_eq!(
+            FractionU32::with_non_negative(1, 2)
+                .unwrap()
+                .checked_div(FractionU32::with_non_negative(0, 1).unwrap()),
+            Err(RationalError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_fraction_pow() {
+        let f = FractionU32::with_negative(2, 3).unwrap();
+        assert_eq!(f.checked_pow(0), Ok(FractionU32::from(1_u32)));
+        assert_eq!(f.checked_pow(3), FractionU32::with_negative(8, 27));
+        assert_eq!(f.checked_pow(-2), FractionU32::with_non_negative(9, 4));
+        assert_eq!(
+            FractionU32::from(0_u32).checked_pow(-1),
+            Err(RationalError::DivideByZero)
+        );
+        assert_eq!(
+            FractionU32::from(2_u32).checked_pow(32),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU32::from(2_u32).checked_pow(-31),
+            FractionU32::with_non_negative(1, 1 << 31)
+        );
+    }
+
     #[test]
     fn test_fraction_ord() {
         assert!(
@@ -271,4 +498,91 @@ mod test {
                 == FractionU32::with_non_negative(0, 3).unwrap()
         );
     }
+
+    #[test]
+    fn test_cross_width() {
+        let a = FractionU8::with_negative(1, 2).unwrap();
+        let b = FractionU16::with_negative(300, 600).unwrap();
+        let c = FractionU32::with_non_negative(70000, 3).unwrap();
+        assert!(a.eq_promoted(&b));
+        assert!(b.eq_promoted(&a));
+        assert!(!a.eq_promoted(&c));
+        assert!(!c.eq_promoted(&b));
+        assert_eq!(a.cmp_promoted(&c), Ordering::Less);
+        assert_eq!(c.cmp_promoted(&a), Ordering::Greater);
+        assert_eq!(b.cmp_promoted(&c), Ordering::Less);
+        assert_eq!(a.cmp_promoted(&b), Ordering::Equal);
+        assert_eq!(
+            FractionU32::from(a),
+            FractionU32::with_negative(1, 2).unwrap()
+        );
+        assert_eq!(a.checked_add_promoted(b), Ok(FractionU16::from(-1_i8)));
+        assert_eq!(b.checked_sub_promoted(a), Ok(FractionU16::from(0_u8)));
+        assert_eq!(
+            c.checked_mul_promoted(a),
+            Ok(FractionU32::with_negative(35000, 3).unwrap())
+        );
+        assert_eq!(
+            a.checked_div_promoted(c),
+            Ok(FractionU32::with_negative(3, 140000).unwrap())
+        );
+        assert_eq!(
+            a.checked_div_promoted(FractionU32::from(0_u32)),
+            Err(RationalError::DivideByZero)
+        );
+        // 较窄类型无法表示结果时，提升后的运算仍然成功
+        let d = FractionU8::with_non_negative(255, 1).unwrap();
+        assert_eq!(
+            d.checked_add_promoted(FractionU16::from(1_u8)),
+            Ok(FractionU16::from(256_u16))
+        );
+        assert_eq!(
+            d.checked_add_promoted(d),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_fraction_int_ops() {
+        let half = FractionU32::with_non_negative(1, 2).unwrap();
+        let neg = FractionU32::with_negative(5, 6).unwrap();
+        assert_eq!(half.checked_add_int(3), Ok(FractionU32::with_non_negative(7, 2).unwrap()));
+        assert_eq!(half.checked_add_int(-1), Ok(FractionU32::with_negative(1, 2).unwrap()));
+        assert_eq!(neg.checked_sub_int(-1), Ok(FractionU32::with_non_negative(1, 6).unwrap()));
+        assert_eq!(neg.checked_mul_int(-4), Ok(FractionU32::with_non_negative(10, 3).unwrap()));
+        assert_eq!(neg.checked_mul_int(0), Ok(FractionU32::from(0_u32)));
+        assert_eq!(neg.checked_div_int(-10), Ok(FractionU32::with_non_negative(1, 12).unwrap()));
+        assert_eq!(half.checked_div_int(0), Err(RationalError::DivideByZero));
+        // 整数本身超出u32，但结果在范围内
+        let big = 6 * i64::from(u32::MAX);
+        let sixth = FractionU32::with_negative(1, 6).unwrap();
+        assert_eq!(
+            sixth.checked_mul_int(big),
+            Ok(FractionU32::with_negative(u32::MAX, 1).unwrap())
+        );
+        assert_eq!(
+            FractionU32::from(u32::MAX).checked_add_int(-2 * i64::from(u32::MAX)),
+            Ok(FractionU32::with_negative(u32::MAX, 1).unwrap())
+        );
+        assert_eq!(half.checked_add_int(i64::MAX), Err(RationalError::NumeratorOverflow));
+        assert_eq!(half.checked_sub_int(i64::MIN), Err(RationalError::NumeratorOverflow));
+        assert_eq!(half.checked_div_int(i64::MIN), Err(RationalError::DenominatorOverflow));
+        // 200/2^63约去8后为25/2^60，分母超出u8
+        assert_eq!(
+            FractionU8::from(200_u8).checked_div_int(i64::MIN),
+            Err(RationalError::DenominatorOverflow)
+        );
+        // 与先转换为分数再运算的结果一致
+        for k in -20..=20_i32 {
+            let f = FractionU32::from(k);
+            for x in [half, neg, sixth] {
+                assert_eq!(x.checked_add_int(k.into()), x.checked_add(f));
+                assert_eq!(x.checked_sub_int(k.into()), x.checked_sub(f));
+                assert_eq!(x.checked_mul_int(k.into()), x.checked_mul(f));
+                if k != 0 {
+                    assert_eq!(x.checked_div_int(k.into()), x.checked_div(f));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,595 @@
+//! poly提供有理系数多项式，所有运算都归结为分数的checked_*运算，溢出时返回错误
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::poly::Polynomial;
+//!
+//! // 2x^2 - 3x + 1 = (2x - 1)(x - 1)
+//! let p = Polynomial::new(vec![FractionU32::from(1_i32), FractionU32::from(-3_i32), FractionU32::from(2_i32)]);
+//! assert_eq!(p.to_string(), "2*x^2 - 3*x + 1");
+//! assert_eq!(
+//!     p.rational_roots().unwrap(),
+//!     [FractionU32::with_non_negative(1, 2).unwrap(), FractionU32::from(1_u32)]
+//! );
+//! ```
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt::Display,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{
+    error::RationalError,
+    frac::{gcd_u128, Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 多项式，系数按次数从低到高保存，最高次系数非零，零多项式没有系数
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial<F> {
+    coeffs: Vec<F>,
+}
+
+fn is_zero<T>(f: &Fraction<T>) -> bool
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    u64::from(f.numer()) == 0
+}
+
+/// 把非负整数转换为分数
+fn integer<T>(v: u64) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    Fraction::try_from(v)
+}
+
+impl<T> Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    /// 由从低到高的系数构造多项式，自动去掉高次的零系数
+    pub fn new(mut coeffs: Vec<Fraction<T>>) -> Self {
+        while coeffs.last().is_some_and(is_zero) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    /// 零多项式
+    pub fn zero() -> Self {
+        Self { coeffs: Vec::new() }
+    }
+
+    /// 系数，从低次到高次
+    pub fn coeffs(&self) -> &[Fraction<T>] {
+        &self.coeffs
+    }
+
+    /// 次数，零多项式没有次数
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// 是否为零多项式
+    pub fn is_zero(&self) -> bool {
+        self.coeffs.is_empty()
+    }
+
+    /// 首项系数
+    pub fn leading(&self) -> Option<Fraction<T>> {
+        self.coeffs.last().copied()
+    }
+
+    /// 用Horner法则在x处求值
+    pub fn eval(&self, x: Fraction<T>) -> Result<Fraction<T>, RationalError> {
+        self.coeffs
+            .iter()
+            .rev()
+            .try_fold(integer(0)?, |acc, &c| acc.checked_mul(x)?.checked_add(c))
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Result<Self, RationalError> {
+        let (long, short) = if self.coeffs.len() >= rhs.coeffs.len() {
+            (self, rhs)
+        } else {
+            (rhs, self)
+        };
+        let mut coeffs = long.coeffs.clone();
+        for (c, &s) in coeffs.iter_mut().zip(short.coeffs.iter()) {
+            *c = c.checked_add(s)?;
+        }
+        Ok(Self::new(coeffs))
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Result<Self, RationalError> {
+        self.checked_add(&-rhs.clone())
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Result<Self, RationalError> {
+        if self.is_zero() || rhs.is_zero() {
+            return Ok(Self::zero());
+        }
+        let mut coeffs = vec![integer(0)?; self.coeffs.len() + rhs.coeffs.len() - 1];
+        for (i, &a) in self.coeffs.iter().enumerate() {
+            for (j, &b) in rhs.coeffs.iter().enumerate() {
+                coeffs[i + j] = coeffs[i + j].checked_add(a.checked_mul(b)?)?;
+            }
+        }
+        Ok(Self::new(coeffs))
+    }
+
+    /// 每个系数乘以k
+    pub fn checked_scale(&self, k: Fraction<T>) -> Result<Self, RationalError> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .map(|c| c.checked_mul(k))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(coeffs))
+    }
+
+    /// 带余除法，返回(商, 余式)，余式次数小于除式
+    pub fn div_rem(&self, divisor: &Self) -> Result<(Self, Self), RationalError> {
+        let Some(lead) = divisor.leading() else {
+            return Err(RationalError::DivideByZero);
+        };
+        let d = divisor.coeffs.len();
+        if self.coeffs.len() < d {
+            return Ok((Self::zero(), self.clone()));
+        }
+        let mut quot = vec![integer(0)?; self.coeffs.len() - d + 1];
+        let mut rem = self.coeffs.clone();
+        while rem.len() >= d {
+            let shift = rem.len() - d;
+            let k = rem[rem.len() - 1].checked_div(lead)?;
+            quot[shift] = k;
+            for (i, &c) in divisor.coeffs.iter().enumerate() {
+                rem[shift + i] = rem[shift + i].checked_sub(k.checked_mul(c)?)?;
+            }
+            // 最高次已被消去
+            rem.pop();
+            while rem.last().is_some_and(is_zero) {
+                rem.pop();
+            }
+        }
+        Ok((Self::new(quot), Self::new(rem)))
+    }
+
+    /// 首一的最大公因式，两个零多项式的最大公因式为零多项式
+    pub fn gcd(&self, other: &Self) -> Result<Self, RationalError> {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_rem(&b)?;
+            a = core::mem::replace(&mut b, r);
+        }
+        match a.leading() {
+            Some(lead) => a.checked_scale(integer(1)?.checked_div(lead)?),
+            None => Ok(a),
+        }
+    }
+
+    /// 求导
+    pub fn derivative(&self) -> Result<Self, RationalError> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, c)| c.checked_mul(integer(i as u64)?))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(coeffs))
+    }
+
+    /// 复合，返回self(inner(x))
+    pub fn compose(&self, inner: &Self) -> Result<Self, RationalError> {
+        self.coeffs.iter().rev().try_fold(Self::zero(), |acc, &c| {
+            acc.checked_mul(inner)?.checked_add(&Self::new(vec![c]))
+        })
+    }
+
+    /// 用有理根定理求出所有互不相同的有理根，按升序排列，零多项式返回OutOfRange
+    pub fn rational_roots(&self) -> Result<Vec<Fraction<T>>, RationalError> {
+        if self.is_zero() {
+            return Err(RationalError::OutOfRange);
+        }
+        let mut roots = Vec::new();
+        // 去掉因子x^k，0是根
+        let low = self.coeffs.iter().take_while(|c| is_zero(c)).count();
+        if low > 0 {
+            roots.push(integer(0)?);
+        }
+        let ints = integer_coeffs(&self.coeffs[low..])?;
+        let (a0, an) = (ints[0].unsigned_abs(), ints[ints.len() - 1].unsigned_abs());
+        if ints.len() > 1 {
+            let bound = ints
+                .iter()
+                .fold(0_u128, |s, c| s.saturating_add(c.unsigned_abs()));
+            let primes = coeff_primes(&self.coeffs[low..]);
+            let qs = divisors(an, &primes, T::MAX.into());
+            for p in divisors(a0, &primes, T::MAX.into()) {
+                for &q in &qs {
+                    if gcd_u128(p, q) != 1 {
+                        continue;
+                    }
+                    for negative in [false, true] {
+                        let p = if negative { -(p as i128) } else { p as i128 };
+                        if is_integer_root(&ints, bound, p, q as i128)? {
+                            roots.push(Wide { n: p, d: q as i128 }.to_coprime_fraction()?);
+                        }
+                    }
+                }
+            }
+        }
+        roots.sort();
+        Ok(roots)
+    }
+}
+
+/// 把有理系数同乘分母的最小公倍数，化为带符号整数系数
+fn integer_coeffs<T>(coeffs: &[Fraction<T>]) -> Result<Vec<i128>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let lcm = coeffs.iter().try_fold(1_u128, |l, c| {
+        let d = u128::from(u64::from(c.denom()));
+        (l / gcd_u128(l, d))
+            .checked_mul(d)
+            .ok_or(RationalError::DenominatorOverflow)
+    })?;
+    coeffs
+        .iter()
+        .map(|c| {
+            let v = u128::from(u64::from(c.numer()))
+                .checked_mul(lcm / u128::from(u64::from(c.denom())))
+                .and_then(|v| i128::try_from(v).ok())
+                .ok_or(RationalError::NumeratorOverflow)?;
+            Ok(match c.sign() {
+                FractionSign::NonNegative => v,
+                FractionSign::Negative => -v,
+            })
+        })
+        .collect()
+}
+
+/// 各系数分子与分母的所有素因子，整数化后的系数只含这些素因子
+fn coeff_primes<T>(coeffs: &[Fraction<T>]) -> Vec<u128>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let mut primes = Vec::new();
+    for c in coeffs {
+        for mut n in [u64::from(c.numer()), u64::from(c.denom())] {
+            // n不超过u32::MAX，最多试除到2^16
+            let mut d = 2;
+            while d * d <= n {
+                if n.is_multiple_of(d) {
+                    primes.push(u128::from(d));
+                    while n.is_multiple_of(d) {
+                        n /= d;
+                    }
+                }
+                d += 1;
+            }
+            if n > 1 {
+                primes.push(u128::from(n));
+            }
+        }
+    }
+    primes.sort_unstable();
+    primes.dedup();
+    primes
+}
+
+/// n不超过max的所有正因数，n的素因子都在primes中，n为0时没有因数
+fn divisors(mut n: u128, primes: &[u128], max: u128) -> Vec<u128> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut result = vec![1_u128];
+    for &p in primes {
+        let mut e = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            e += 1;
+        }
+        for i in 0..result.len() {
+            let mut v = result[i];
+            for _ in 0..e {
+                match v.checked_mul(p).filter(|&v| v <= max) {
+                    Some(next) => v = next,
+                    None => break,
+                }
+                result.push(v);
+            }
+        }
+    }
+    debug_assert_eq!(n, 1);
+    result
+}
+
+/// 用综合除法判断p/q是否是整系数多项式的根，各步必须整除。
+/// p/q是根时商的各系数都不超过bound（各系数绝对值之和），超出即可排除，不必等到溢出
+fn is_integer_root(ints: &[i128], bound: u128, p: i128, q: i128) -> Result<bool, RationalError> {
+    // 用(qx - p)去除，商的系数都是整数
+    let mut b = 0_i128;
+    for (i, &c) in ints.iter().enumerate().rev() {
+        if b.unsigned_abs() > bound {
+            return Ok(false);
+        }
+        let v = p
+            .checked_mul(b)
+            .and_then(|pb| c.checked_add(pb))
+            .ok_or(RationalError::NumeratorOverflow)?;
+        if i == 0 {
+            return Ok(v == 0);
+        }
+        if v % q != 0 {
+            return Ok(false);
+        }
+        b = v / q;
+    }
+    Ok(false)
+}
+
+/// 打印为`2*x^2 - 1/2*x + 3`形式，零多项式打印为`0`
+impl<T> Display for Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.coeffs.is_empty() {
+            return write!(f, "0");
+        }
+        let mut first = true;
+        for (i, c) in self.coeffs.iter().enumerate().rev() {
+            if is_zero(c) {
+                continue;
+            }
+            let negative = c.sign() == FractionSign::Negative;
+            match (first, negative) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            first = false;
+            let abs = Fraction::new(c.numer(), c.denom(), FractionSign::NonNegative)
+                .map_err(|_| core::fmt::Error)?;
+            let unit = u64::from(c.numer()) == 1 && u64::from(c.denom()) == 1;
+            match (i, unit) {
+                (0, _) => write!(f, "{abs}")?,
+                (1, true) => write!(f, "x")?,
+                (1, false) => write!(f, "{abs}*x")?,
+                (_, true) => write!(f, "x^{i}")?,
+                (_, false) => write!(f, "{abs}*x^{i}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 取负
+impl<T> Neg for Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            coeffs: self.coeffs.into_iter().map(|c| -c).collect(),
+        }
+    }
+}
+
+/// 加法
+impl<T> Add for Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 减法
+impl<T> Sub for Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(&rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+/// 乘法
+impl<T> Mul for Polynomial<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+    u32: From<T>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(&rhs)
+            .expect("numerator/denominator overflow")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use crate::{
+        frac::{FractionU32, FractionU8},
+        test_util::frac,
+    };
+
+    use super::*;
+
+    /// 由整数系数构造多项式，系数从低到高
+    fn poly(coeffs: &[i64]) -> Polynomial<FractionU32> {
+        Polynomial::new(coeffs.iter().map(|&c| frac(c, 1)).collect())
+    }
+
+    #[test]
+    fn test_new_and_display() {
+        assert_eq!(poly(&[1, 2, 0, 0]).degree(), Some(1));
+        assert_eq!(poly(&[0, 0]), Polynomial::zero());
+        assert_eq!(poly(&[0]).degree(), None);
+        assert_eq!(poly(&[3, -1, 0, 1]).to_string(), "x^3 - x + 3");
+        assert_eq!(
+            Polynomial::new(vec![frac(0, 1), frac(-1, 2), frac(-2, 3)]).to_string(),
+            "-2/3*x^2 - 1/2*x"
+        );
+        assert_eq!(Polynomial::<FractionU32>::zero().to_string(), "0");
+    }
+
+    #[test]
+    fn test_eval() {
+        let p = Polynomial::new(vec![frac(1, 4), frac(-1, 2), frac(1, 1)]);
+        assert_eq!(p.eval(frac(1, 2)), Ok(frac(1, 4)));
+        assert_eq!(p.eval(frac(0, 1)), Ok(frac(1, 4)));
+        assert_eq!(
+            Polynomial::<FractionU32>::zero().eval(frac(3, 1)),
+            Ok(frac(0, 1))
+        );
+        let q = Polynomial::new(vec![
+            FractionU8::from(0_u8),
+            FractionU8::from(0_u8),
+            FractionU8::from(1_u8),
+        ]);
+        assert_eq!(
+            q.eval(FractionU8::from(16_u8)),
+            Err(RationalError::NumeratorOverflow)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = poly(&[1, 1]);
+        let b = poly(&[-1, 1]);
+        assert_eq!(a.clone() * b.clone(), poly(&[-1, 0, 1]));
+        assert_eq!(a.clone() + b.clone(), poly(&[0, 2]));
+        assert_eq!(a.clone() - a.clone(), Polynomial::zero());
+        assert_eq!(a - b, poly(&[2]));
+    }
+
+    #[test]
+    fn test_div_rem() {
+        // x^3 - 2x^2 - 4 = (x - 3)(x^2 + x + 3) + 5
+        let (q, r) = poly(&[-4, 0, -2, 1]).div_rem(&poly(&[-3, 1])).unwrap();
+        assert_eq!(q, poly(&[3, 1, 1]));
+        assert_eq!(r, poly(&[5]));
+        // x^2 + 1 除以 2x = (1/2 x) * 2x + 1
+        let (q, r) = poly(&[1, 0, 1]).div_rem(&poly(&[0, 2])).unwrap();
+        assert_eq!(q, Polynomial::new(vec![frac(0, 1), frac(1, 2)]));
+        assert_eq!(r, poly(&[1]));
+        let (q, r) = poly(&[1, 1]).div_rem(&poly(&[0, 0, 1])).unwrap();
+        assert_eq!((q, r), (Polynomial::zero(), poly(&[1, 1])));
+        assert_eq!(
+            poly(&[1]).div_rem(&Polynomial::zero()),
+            Err(RationalError::DivideByZero)
+        );
+    }
+
+    #[test]
+    fn test_gcd() {
+        // (x - 1)(x + 2) 与 (x - 1)(2x + 3)
+        let a = poly(&[-2, 1, 1]);
+        let b = poly(&[-3, 1, 2]);
+        assert_eq!(a.gcd(&b), Ok(poly(&[-1, 1])));
+        assert_eq!(a.gcd(&poly(&[5])), Ok(poly(&[1])));
+        assert_eq!(poly(&[0, 4]).gcd(&Polynomial::zero()), Ok(poly(&[0, 1])));
+    }
+
+    #[test]
+    fn test_derivative_and_compose() {
+        let p = poly(&[5, 3, 0, 2]);
+        assert_eq!(p.derivative(), Ok(poly(&[3, 0, 6])));
+        assert_eq!(poly(&[7]).derivative(), Ok(Polynomial::zero()));
+        // p(x) = x^2 + 1, q(x) = x - 1, p(q(x)) = x^2 - 2x + 2
+        assert_eq!(
+            poly(&[1, 0, 1]).compose(&poly(&[-1, 1])),
+            Ok(poly(&[2, -2, 1]))
+        );
+        assert_eq!(
+            poly(&[-1, 1]).compose(&poly(&[1, 0, 1])),
+            Ok(poly(&[0, 0, 1]))
+        );
+    }
+
+    #[test]
+    fn test_rational_roots() {
+        // 6x^3 - 11x^2 + 6x - 1 = (x - 1)(2x - 1)(3x - 1)
+        assert_eq!(
+            poly(&[-1, 6, -11, 6]).rational_roots(),
+            Ok(vec![frac(1, 3), frac(1, 2), frac(1, 1)])
+        );
+        // x^3 + x^2 - 2x = x(x - 1)(x + 2)
+        assert_eq!(
+            poly(&[0, -2, 1, 1]).rational_roots(),
+            Ok(vec![frac(-2, 1), frac(0, 1), frac(1, 1)])
+        );
+        // (x - 1)^2 (x^2 + 1)，重根只出现一次，无理根被忽略
+        assert_eq!(
+            poly(&[1, -2, 2, -2, 1]).rational_roots(),
+            Ok(vec![frac(1, 1)])
+        );
+        // 1/2 x - 3/4
+        assert_eq!(
+            Polynomial::new(vec![frac(-3, 4), frac(1, 2)]).rational_roots(),
+            Ok(vec![frac(3, 2)])
+        );
+        assert_eq!(poly(&[0, 0, 2]).rational_roots(), Ok(vec![frac(0, 1)]));
+        assert_eq!(poly(&[3]).rational_roots(), Ok(vec![]));
+        assert_eq!(
+            Polynomial::<FractionU32>::zero().rational_roots(),
+            Err(RationalError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn test_rational_roots_with_large_coeffs() {
+        // 4294967291是素数，候选根4294967291的5次方超出i128，但它显然不是根
+        assert_eq!(
+            poly(&[-4294967291, 0, 0, 0, 0, 1]).rational_roots(),
+            Ok(vec![])
+        );
+        assert_eq!(
+            poly(&[-4294967291, 1]).rational_roots(),
+            Ok(vec![frac(4294967291, 1)])
+        );
+        // 整数化后各系数都是两个大素数之积，只从系数的分子分母中取素因子，不必逐个试除
+        let p = Polynomial::new(vec![
+            frac(1, 4294967291),
+            frac(1, 4294967279),
+            frac(1, 4294967231),
+        ]);
+        assert_eq!(p.rational_roots(), Ok(vec![]));
+        // 根的分子分母都是大素数
+        let q = Polynomial::new(vec![frac(-4294967279, 4294967291), frac(1, 1)]);
+        assert_eq!(q.rational_roots(), Ok(vec![frac(4294967279, 4294967291)]));
+    }
+}
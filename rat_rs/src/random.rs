@@ -0,0 +1,570 @@
+//! random在`rand` feature下为分数提供随机采样分布，所有样本都经由`Fraction::new`构造，因此总是约分且规范的
+//!
+//! # Example
+//! ```rust
+//! use rand::{distributions::Distribution, rngs::SmallRng, SeedableRng};
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::random::{RandomReduced, UniformFraction};
+//!
+//! let mut rng = SmallRng::seed_from_u64(7);
+//! let lo = FractionU32::with_negative(1, 2).unwrap();
+//! let hi = FractionU32::with_non_negative(1, 2).unwrap();
+//! let uniform = UniformFraction::new(lo, hi, 100).unwrap();
+//! let f = uniform.sample(&mut rng);
+//! assert!(lo <= f && f <= hi && f.denom() <= 100);
+//!
+//! let reduced = RandomReduced::<u32>::signed(16, 8).unwrap();
+//! let g = reduced.sample(&mut rng);
+//! assert!(g.numer() < 1 << 16 && g.denom() < 1 << 8);
+//! ```
+
+use core::{cmp::Ordering, marker::PhantomData};
+
+use rand::{distributions::Distribution, Rng};
+
+use crate::{
+    error::RationalError,
+    frac::{gcd, Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 候选分数不超过该个数时预先枚举
+const ENUM_LIMIT: usize = 64;
+
+/// 在闭区间[lo, hi]内分母不超过max_denom的既约分数上均匀采样
+///
+/// 区间不含0时，max_denom先收紧到分子不超过T::MAX所允许的最大分母。
+/// 候选分数不超过64个时（包括lo == hi）在构造时全部枚举，每次采样O(1)；
+/// 否则在满足lo·q ≤ p ≤ hi·q、|p| ≤ T::MAX、q ≤ max_denom的格点(q, p)上均匀选取，未约分时重新选取。
+/// 每个格点都是某个候选的倍数，区间内分母最小的分数的倍数可能远多于候选本身，因此不参与选取；
+/// 其余格点中既约分数通常约占6/π²，期望尝试次数为常数。
+/// 选取一个格点需要对分母二分查找，每步用floor sum计算前缀格点数，
+/// 因此每次尝试的代价为O(log²(max_denom))
+#[derive(Debug, Clone, Copy)]
+pub struct UniformFraction<T> {
+    lo: Wide,
+    hi: Wide,
+    max_denom: u64,
+    /// 区间内分母最小的分数(a, b)，其倍数(ka, kb), k ≥ 2不参与选取
+    simplest: (i128, i128),
+    /// 参与选取的格点个数
+    count: i128,
+    /// 预先枚举的候选分数，仅前len个有效，len为0时在格点上拒绝采样
+    points: [Fraction<T>; ENUM_LIMIT],
+    len: usize,
+}
+
+/// 在分母不超过q的既约分数（Farey序列F_q）中，返回不小于x=n/m的第一项及其前驱，
+/// 沿Stern–Brocot树成批下降，步数为O(log q)
+fn farey_start(x: (i128, i128), q: i128) -> ((i128, i128), (i128, i128)) {
+    let (n, m) = x;
+    let a = n.div_euclid(m);
+    if n.rem_euclid(m) == 0 {
+        return ((a * q - 1, q), (a, 1));
+    }
+    let (mut l, mut r) = ((a, 1), (a + 1, 1));
+    loop {
+        let med = (l.0 + r.0, l.1 + r.1);
+        if med.1 > q {
+            return (l, r);
+        }
+        match (med.0 * m).cmp(&(n * med.1)) {
+            Ordering::Equal => {
+                let k = (q - l.1) / m;
+                return ((l.0 + k * n, l.1 + k * m), (n, m));
+            }
+            Ordering::Less => {
+                let k = ((n * l.1 - l.0 * m - 1) / (r.0 * m - n * r.1)).min((q - l.1) / r.1);
+                l = (l.0 + k * r.0, l.1 + k * r.1);
+            }
+            Ordering::Greater => {
+                let k = ((r.0 * m - n * r.1 - 1) / (n * l.1 - l.0 * m)).min((q - r.1) / l.1);
+                r = (r.0 + k * l.0, r.1 + k * l.1);
+            }
+        }
+    }
+}
+
+/// Σ_{i=0}^{n-1} ⌊(a·i + b)/m⌋，要求n ≥ 0且m > 0，辗转相除，步数为O(log m)
+fn floor_sum(mut n: i128, mut m: i128, mut a: i128, mut b: i128) -> i128 {
+    let mut sum = 0;
+    if a < 0 {
+        let r = a.rem_euclid(m);
+        sum -= n * (n - 1) / 2 * ((r - a) / m);
+        a = r;
+    }
+    if b < 0 {
+        let r = b.rem_euclid(m);
+        sum -= n * ((r - b) / m);
+        b = r;
+    }
+    loop {
+        if a >= m {
+            sum += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+        if b >= m {
+            sum += n * (b / m);
+            b %= m;
+        }
+        let y = a * n + b;
+        if y < m {
+            return sum;
+        }
+        (n, b) = (y / m, y % m);
+        (m, a) = (a, m);
+    }
+}
+
+/// Σ_{q=1}^{x} min(⌊r·q⌋, cap)
+fn capped_floor_sum(r: Wide, x: i128, cap: i128) -> i128 {
+    // r·q超过cap之后每项都是cap
+    let t = if r.n > 0 { x.min(cap * r.d / r.n) } else { x };
+    floor_sum(t, r.d, r.n, r.n) + cap * (x - t)
+}
+
+/// 带符号的既约分子分母转为分数，无法放入T时返回None
+fn to_fraction<T>((p, q): (i128, i128)) -> Option<Fraction<T>>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    Wide { n: p, d: q }.to_coprime_fraction().ok()
+}
+
+impl<T> UniformFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    /// 区间内不存在分母不超过max_denom且能放入T的分数时返回EmptyInterval
+    pub fn new(lo: Fraction<T>, hi: Fraction<T>, max_denom: T) -> Result<Self, RationalError> {
+        let mut max_denom = u64::from(max_denom);
+        if lo > hi || max_denom == 0 {
+            return Err(RationalError::EmptyInterval);
+        }
+        let (mut points, bounds) = ([lo; ENUM_LIMIT], (lo, hi));
+        let (lo, hi) = (Wide::from_fraction(&lo), Wide::from_fraction(&hi));
+        // 区间不含0时，分子不超过T::MAX限制了可能出现的分母
+        let nearest = match (lo.n > 0, hi.n < 0) {
+            (true, _) => Some(lo),
+            (_, true) => Some(hi.neg()),
+            _ => None,
+        };
+        if let Some(Wide { n, d: m }) = nearest {
+            let limit = i128::from(T::MAX) * m / n;
+            max_denom = max_denom.min(u64::try_from(limit).unwrap_or(u64::MAX));
+        }
+        // 沿F_q逐项枚举区间内的分数，超出ENUM_LIMIT个时改用拒绝采样
+        let q = i128::from(max_denom);
+        let (mut prev, mut cur) = farey_start((lo.n, lo.d), q);
+        let (mut len, mut total) = (0, 0);
+        while total <= ENUM_LIMIT && cur.0 * hi.d <= hi.n * cur.1 {
+            if let Some(f) = to_fraction(cur).filter(|_| total < ENUM_LIMIT) {
+                points[len] = f;
+                len += 1;
+            }
+            total += 1;
+            let k = (q + prev.1) / cur.1;
+            (prev, cur) = (cur, (k * cur.0 - prev.0, k * cur.1 - prev.1));
+        }
+        if total <= ENUM_LIMIT {
+            if len == 0 {
+                return Err(RationalError::EmptyInterval);
+            }
+        } else {
+            len = 0;
+        }
+        let mut uniform = Self {
+            lo,
+            hi,
+            max_denom,
+            simplest: (0, 1),
+            count: 0,
+            points,
+            len,
+        };
+        if len == 0 {
+            let simplest = Fraction::simplest_between(bounds.0, bounds.1)?;
+            uniform.simplest = (
+                simplest.signed_numer(),
+                i128::from(u64::from(simplest.denom())),
+            );
+            uniform.count = uniform.prefix(q);
+        }
+        Ok(uniform)
+    }
+
+    /// 在Farey序列F_n，即[0, 1]内分母不超过n的既约分数上均匀采样
+    pub fn farey(n: T) -> Result<Self, RationalError> {
+        let zero = Fraction::try_from(0_u64)?;
+        let one = Fraction::try_from(1_u64)?;
+        Self::new(zero, one, n)
+    }
+}
+
+impl<T> UniformFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 分母为q的一列中最小的分子，以及该列是否含有最简分数的倍数及其分子
+    fn column(&self, q: i128) -> (i128, Option<i128>) {
+        let cap = i128::from(T::MAX);
+        let first = -(-self.lo.n * q).div_euclid(self.lo.d).min(cap);
+        let (a, b) = self.simplest;
+        let k = q / b;
+        let on_ray = q % b == 0 && k >= 2 && (a * k).abs() <= cap;
+        (first, on_ray.then_some(a * k))
+    }
+
+    /// 分母不超过x的参与选取的格点个数
+    fn prefix(&self, x: i128) -> i128 {
+        let cap = i128::from(T::MAX);
+        let (a, b) = self.simplest;
+        let multiples = if a == 0 {
+            x / b
+        } else {
+            (x / b).min(cap / a.abs())
+        };
+        capped_floor_sum(self.hi, x, cap) + capped_floor_sum(self.lo.neg(), x, cap) + x
+            - (multiples - 1).max(0)
+    }
+
+    /// 均匀选取一个格点，未约分时返回None
+    fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<Fraction<T>> {
+        let r = rng.gen_range(0..self.count);
+        // 二分查找使prefix(q) > r的最小分母
+        let (mut lo, mut hi) = (1, i128::from(self.max_denom));
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if self.prefix(mid) > r {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        let q = lo;
+        let (first, multiple) = self.column(q);
+        let mut p = first + r - self.prefix(q - 1);
+        if multiple.is_some_and(|m| p >= m) {
+            p += 1;
+        }
+        let numer = u64::try_from(p.unsigned_abs()).ok()?;
+        if gcd(numer, q as u64) != 1 {
+            return None;
+        }
+        to_fraction((p, q))
+    }
+}
+
+/// 候选已枚举时直接均匀选取一项；否则均匀选取格点直到得到既约分数，
+/// 每个既约分数恰好对应一个格点，因此被选中的概率相同
+impl<T> Distribution<Fraction<T>> for UniformFraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fraction<T> {
+        if self.len > 0 {
+            return self.points[rng.gen_range(0..self.len)];
+        }
+        loop {
+            if let Some(f) = self.try_sample(rng) {
+                return f;
+            }
+        }
+    }
+}
+
+/// 随机既约分数，分子小于2^numer_bits，分母在[1, 2^denom_bits)内
+#[derive(Debug, Clone, Copy)]
+pub struct RandomReduced<T> {
+    numer_max: u64,
+    denom_max: u64,
+    signed: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T> RandomReduced<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 位宽超出T或分母位宽为0时返回OutOfRange
+    fn with_sign(numer_bits: u32, denom_bits: u32, signed: bool) -> Result<Self, RationalError> {
+        let bits = T::MAX.count_ones();
+        if numer_bits > bits || denom_bits > bits || denom_bits == 0 {
+            return Err(RationalError::OutOfRange);
+        }
+        let max = |b: u32| ((1_u128 << b) - 1) as u64;
+        Ok(Self {
+            numer_max: max(numer_bits),
+            denom_max: max(denom_bits),
+            signed,
+            marker: PhantomData,
+        })
+    }
+
+    /// 非负的随机既约分数
+    pub fn non_negative(numer_bits: u32, denom_bits: u32) -> Result<Self, RationalError> {
+        Self::with_sign(numer_bits, denom_bits, false)
+    }
+
+    /// 符号随机的既约分数
+    pub fn signed(numer_bits: u32, denom_bits: u32) -> Result<Self, RationalError> {
+        Self::with_sign(numer_bits, denom_bits, true)
+    }
+}
+
+/// 均匀选取分子与分母，不互素时重新采样
+impl<T> Distribution<Fraction<T>> for RandomReduced<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Fraction<T> {
+        loop {
+            let numer = rng.gen_range(0..=self.numer_max);
+            let denom = rng.gen_range(1..=self.denom_max);
+            if gcd(numer, denom) != 1 {
+                continue;
+            }
+            let sign = if self.signed && rng.gen() {
+                FractionSign::Negative
+            } else {
+                FractionSign::NonNegative
+            };
+            let (Ok(numer), Ok(denom)) = (T::try_from(numer), T::try_from(denom)) else {
+                continue;
+            };
+            if let Ok(f) = Fraction::new(numer, denom, sign) {
+                return f;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use std::collections::BTreeMap;
+
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use crate::{
+        farey::Farey,
+        frac::{FractionU16, FractionU32, FractionU8},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_uniform_in_range() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let lo = FractionU32::with_negative(7, 3).unwrap();
+        let hi = FractionU32::with_non_negative(5, 4).unwrap();
+        let uniform = UniformFraction::new(lo, hi, 50).unwrap();
+        for f in uniform.sample_iter(&mut rng).take(2000) {
+            assert!(lo <= f && f <= hi);
+            assert!(f.denom() <= 50);
+            assert_eq!(Fraction::new(f.numer(), f.denom(), f.sign()), Ok(f));
+        }
+    }
+
+    #[test]
+    fn test_uniform_empty() {
+        let lo = FractionU32::with_non_negative(1, 3).unwrap();
+        let hi = FractionU32::with_non_negative(2, 5).unwrap();
+        assert_eq!(
+            UniformFraction::new(hi, lo, 10).unwrap_err(),
+            RationalError::EmptyInterval
+        );
+        // [103/300, 39/100]内分母最小的分数是3/8
+        assert_eq!(
+            UniformFraction::new(
+                FractionU32::with_non_negative(103, 300).unwrap(),
+                FractionU32::with_non_negative(39, 100).unwrap(),
+                7
+            )
+            .unwrap_err(),
+            RationalError::EmptyInterval
+        );
+        assert!(UniformFraction::new(lo, lo, 3).is_ok());
+        let mut rng = SmallRng::seed_from_u64(2);
+        assert_eq!(
+            UniformFraction::new(lo, lo, 3).unwrap().sample(&mut rng),
+            lo
+        );
+    }
+
+    /// 逐个分母列出[lo, hi]内分母不超过max_denom且能放入u16的既约分数
+    fn brute_force(
+        lo: (i128, i128),
+        hi: (i128, i128),
+        max_denom: i128,
+    ) -> std::vec::Vec<FractionU16> {
+        let mut all = std::vec::Vec::new();
+        for q in 1..=max_denom {
+            let first = (lo.0 * q).div_euclid(lo.1) + i128::from((lo.0 * q).rem_euclid(lo.1) != 0);
+            let last = (hi.0 * q).div_euclid(hi.1);
+            for p in first.max(-65535)..=last.min(65535) {
+                if gcd(p.unsigned_abs() as u64, q as u64) == 1 {
+                    all.extend(to_fraction((p, q)));
+                }
+            }
+        }
+        all.sort();
+        all
+    }
+
+    #[test]
+    fn test_farey_start() {
+        for q in 1..8 {
+            let terms = brute_force((-3, 1), (3, 1), q);
+            for x in [(-5, 2), (-1, 3), (0, 1), (2, 7), (3, 8), (1, 1), (11, 5)] {
+                let (prev, first) = farey_start(x, q);
+                let i = terms.partition_point(|f| {
+                    Wide::from_fraction(f).n * x.1 < x.0 * Wide::from_fraction(f).d
+                });
+                assert_eq!(to_fraction(first), Some(terms[i]));
+                assert_eq!(to_fraction(prev), Some(terms[i - 1]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniform_narrow() {
+        // 区间内只有极少数候选时逐项枚举，而不是在max_denom个分母上拒绝采样
+        let mut rng = SmallRng::seed_from_u64(5);
+        let third = FractionU32::with_non_negative(1, 3).unwrap();
+        let uniform = UniformFraction::new(third, third, u32::MAX).unwrap();
+        assert!(uniform.sample_iter(&mut rng).take(100).all(|f| f == third));
+        for (lo, hi) in [
+            ((1, 3), (21845, 65534)),
+            ((-21845, 65534), (-1, 3)),
+            ((-1, 65535), (1, 65535)),
+            ((4681, 1), (65535, 14)),
+        ] {
+            let f = |(n, d): (i128, i128)| to_fraction::<u16>((n, d)).unwrap();
+            let uniform = UniformFraction::new(f(lo), f(hi), u16::MAX).unwrap();
+            let expected = brute_force(lo, hi, i128::from(u16::MAX));
+            assert_eq!(&uniform.points[..uniform.len], expected);
+            let sampled: std::collections::BTreeSet<_> =
+                uniform.sample_iter(&mut rng).take(200).collect();
+            assert!(sampled.iter().eq(expected.iter()));
+        }
+    }
+
+    #[test]
+    fn test_lattice_is_uniform() {
+        let mut rng = SmallRng::seed_from_u64(6);
+        for (lo, hi, max_denom) in [((-3, 2), (5, 4), 12), ((1, 5), (1, 3), 40)] {
+            let f = |(n, d): (i128, i128)| to_fraction::<u16>((n, d)).unwrap();
+            let uniform = UniformFraction::new(f(lo), f(hi), max_denom).unwrap();
+            assert_eq!(uniform.len, 0);
+            // 格点个数：每列的整数分子，减去最简分数的k ≥ 2倍
+            let (a, b) = uniform.simplest;
+            let count: i128 = (1..=i128::from(max_denom))
+                .map(|q| {
+                    let first =
+                        (lo.0 * q).div_euclid(lo.1) + i128::from((lo.0 * q).rem_euclid(lo.1) != 0);
+                    let last = (hi.0 * q).div_euclid(hi.1);
+                    let multiple =
+                        q % b == 0 && q / b >= 2 && (first..=last).contains(&(a * q / b));
+                    last - first + 1 - i128::from(multiple)
+                })
+                .sum();
+            assert_eq!(uniform.count, count);
+            let expected = brute_force(lo, hi, i128::from(max_denom));
+            let mut counts = BTreeMap::new();
+            let per_candidate = 400;
+            for f in uniform
+                .sample_iter(&mut rng)
+                .take(per_candidate * expected.len())
+            {
+                *counts.entry(f).or_insert(0_u32) += 1;
+            }
+            assert!(counts.keys().eq(expected.iter()));
+            for &c in counts.values() {
+                assert!((300..500).contains(&c), "{c}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_uniform_rejection_rate() {
+        // 候选很少而max_denom很大时，按分母均匀拒绝采样每次要尝试约max_denom/N次
+        let mut rng = SmallRng::seed_from_u64(7);
+        let f = |n: u32, d: u32| FractionU32::with_non_negative(n, d).unwrap();
+        let big = 1 << 28;
+        for (lo, hi) in [
+            // 约80个候选
+            (f(1, big), f(1, big - 1)),
+            // 1/3的倍数占了格点的大部分
+            (f(1, 3), f(33554433, 100663296)),
+            (f(1, 1), f(1 << 31, 1)),
+            (
+                FractionU32::with_negative(1, 1 << 31).unwrap(),
+                f(1, 1 << 31),
+            ),
+        ] {
+            let uniform = UniformFraction::new(lo, hi, u32::MAX).unwrap();
+            assert_eq!(uniform.len, 0);
+            let attempts = 1000;
+            let accepted = (0..attempts)
+                .filter_map(|_| uniform.try_sample(&mut rng))
+                .inspect(|f| assert!(lo <= *f && *f <= hi))
+                .count();
+            assert!(accepted * 3 >= attempts, "{accepted}");
+        }
+    }
+
+    #[test]
+    fn test_farey_is_uniform() {
+        let mut rng = SmallRng::seed_from_u64(3);
+        let farey = UniformFraction::<u8>::farey(6).unwrap();
+        let mut counts = BTreeMap::new();
+        let samples = 26000;
+        for f in farey.sample_iter(&mut rng).take(samples) {
+            *counts.entry(f).or_insert(0_u32) += 1;
+        }
+        // F_6共有13项，每项期望2000次
+        let expected: std::vec::Vec<FractionU8> = Farey::new(6_u8).unwrap().collect();
+        assert_eq!(
+            counts.keys().copied().collect::<std::vec::Vec<_>>(),
+            expected
+        );
+        for &c in counts.values() {
+            assert!((1800..2200).contains(&c), "{c}");
+        }
+    }
+
+    #[test]
+    fn test_random_reduced() {
+        let mut rng = SmallRng::seed_from_u64(4);
+        let reduced = RandomReduced::<u16>::signed(12, 5).unwrap();
+        let mut negative = 0;
+        for f in reduced.sample_iter(&mut rng).take(2000) {
+            assert!(f.numer() < 1 << 12);
+            assert!(f.denom() >= 1 && f.denom() < 1 << 5);
+            assert_eq!(gcd(f.numer().into(), f.denom().into()), 1);
+            negative += usize::from(f.sign() == FractionSign::Negative);
+        }
+        assert!((800..1200).contains(&negative));
+        let reduced = RandomReduced::<u8>::non_negative(8, 8).unwrap();
+        assert!(reduced
+            .sample_iter(&mut rng)
+            .take(500)
+            .all(|f: FractionU8| f.sign() == FractionSign::NonNegative));
+        assert_eq!(
+            RandomReduced::<u16>::signed(17, 4).unwrap_err(),
+            RationalError::OutOfRange
+        );
+        assert_eq!(
+            RandomReduced::<u16>::signed(4, 0).unwrap_err(),
+            RationalError::OutOfRange
+        );
+    }
+}
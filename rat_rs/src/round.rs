@@ -0,0 +1,214 @@
+//! round定义舍入模式，供定分母分数与定点数转换等需要舍入的运算使用，
+//! 并提供分数与十进制定点整数之间的转换，用于对接货币金额与数据库DECIMAL列
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::round::RoundingMode;
+//!
+//! let f = FractionU32::with_non_negative(1, 3).unwrap();
+//! assert_eq!(f.to_fixed(2, RoundingMode::HalfEven), Ok(33));
+//! assert_eq!(f.to_fixed(2, RoundingMode::Ceiling), Ok(34));
+//! assert_eq!(
+//!     FractionU32::from_fixed(-1250, 3),
+//!     Ok(FractionU32::with_negative(5, 4).unwrap())
+//! );
+//! ```
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 舍入模式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RoundingMode {
+    /// 向零舍入
+    Down,
+    /// 远离零舍入
+    Up,
+    /// 向负无穷舍入
+    Floor,
+    /// 向正无穷舍入
+    Ceiling,
+    /// 四舍五入，恰好一半时远离零
+    HalfUp,
+    /// 恰好一半时向零
+    HalfDown,
+    /// 恰好一半时取偶数，即银行家舍入
+    HalfEven,
+}
+
+/// 按舍入模式计算±n/d的绝对值，negative表示结果的符号
+pub(crate) fn div_round(n: u128, d: u128, negative: bool, mode: RoundingMode) -> u128 {
+    let (q, r) = (n / d, n % d);
+    if r == 0 {
+        return q;
+    }
+    // 比较余数与d的一半，r < d因此2r不会溢出
+    let half = (2 * r).cmp(&d);
+    let increment = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::Floor => negative,
+        RoundingMode::Ceiling => !negative,
+        RoundingMode::HalfUp => half.is_ge(),
+        RoundingMode::HalfDown => half.is_gt(),
+        RoundingMode::HalfEven => half.is_gt() || (half.is_eq() && q % 2 == 1),
+    };
+    q + u128::from(increment)
+}
+
+impl<T> Fraction<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    /// 乘以10^scale后按mode舍入为整数，结果超出i64时返回NumeratorOverflow
+    pub fn to_fixed(&self, scale: u32, mode: RoundingMode) -> Result<i64, RationalError> {
+        let negative = self.sign() == FractionSign::Negative;
+        let n = 10_u128
+            .checked_pow(scale)
+            .and_then(|p| p.checked_mul(u128::from(u64::from(self.numer()))))
+            .ok_or(RationalError::NumeratorOverflow)?;
+        let n = div_round(n, u128::from(u64::from(self.denom())), negative, mode);
+        let n = i128::try_from(n).map_err(|_| RationalError::NumeratorOverflow)?;
+        i64::try_from(if negative { -n } else { n }).map_err(|_| RationalError::NumeratorOverflow)
+    }
+
+    /// 定点整数value/10^scale对应的分数，约分后仍超出T时返回溢出错误，value为0时不论scale都返回0
+    pub fn from_fixed(value: i64, scale: u32) -> Result<Self, RationalError> {
+        if value == 0 {
+            return Wide::ZERO.to_coprime_fraction();
+        }
+        let denom = 10_i128
+            .checked_pow(scale)
+            .ok_or(RationalError::DenominatorOverflow)?;
+        Wide::reduced(i128::from(value), denom).to_coprime_fraction()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        frac::{FractionU32, FractionU8},
+        test_util::frac,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_div_round() {
+        use RoundingMode::*;
+
+        // 5/2 = 2.5, 7/3 = 2.33.., 8/3 = 2.66..
+        let cases = [
+            (Down, [2, 2, 2], [2, 2, 2]),
+            (Up, [3, 3, 3], [3, 3, 3]),
+            (Floor, [2, 2, 2], [3, 3, 3]),
+            (Ceiling, [3, 3, 3], [2, 2, 2]),
+            (HalfUp, [3, 2, 3], [3, 2, 3]),
+            (HalfDown, [2, 2, 3], [2, 2, 3]),
+            (HalfEven, [2, 2, 3], [2, 2, 3]),
+        ];
+        for (mode, pos, neg) in cases {
+            for (i, (n, d)) in [(5, 2), (7, 3), (8, 3)].into_iter().enumerate() {
+                assert_eq!(div_round(n, d, false, mode), pos[i], "{mode:?} {n}/{d}");
+                assert_eq!(div_round(n, d, true, mode), neg[i], "{mode:?} -{n}/{d}");
+            }
+            assert_eq!(div_round(6, 3, false, mode), 2);
+        }
+        assert_eq!(div_round(7, 2, false, HalfEven), 4);
+    }
+
+    /// 所有舍入模式下，各个恰好一半的值的期望结果，依次为-2.5, -1.5, -0.5, 0.5, 1.5, 2.5
+    const MIDPOINTS: [(RoundingMode, [i64; 6]); 7] = [
+        (RoundingMode::Down, [-2, -1, 0, 0, 1, 2]),
+        (RoundingMode::Up, [-3, -2, -1, 1, 2, 3]),
+        (RoundingMode::Floor, [-3, -2, -1, 0, 1, 2]),
+        (RoundingMode::Ceiling, [-2, -1, 0, 1, 2, 3]),
+        (RoundingMode::HalfUp, [-3, -2, -1, 1, 2, 3]),
+        (RoundingMode::HalfDown, [-2, -1, 0, 0, 1, 2]),
+        (RoundingMode::HalfEven, [-2, -2, 0, 0, 2, 2]),
+    ];
+
+    #[test]
+    fn test_to_fixed_midpoints() {
+        let halves = [-5, -3, -1, 1, 3, 5].map(|n| frac(n, 2));
+        for (mode, expected) in MIDPOINTS {
+            for (f, e) in halves.iter().zip(expected) {
+                assert_eq!(f.to_fixed(0, mode), Ok(e), "{mode:?} {f}");
+                // 放大小数位后同样恰好位于一半：x.xx5
+                let g = *f / FractionU32::from(100_u32);
+                assert_eq!(g.to_fixed(2, mode), Ok(e), "{mode:?} {g}");
+                let h = *f / FractionU32::from(1000_u32);
+                assert_eq!(h.to_fixed(3, mode), Ok(e), "{mode:?} {h}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_fixed() {
+        let third = FractionU32::with_non_negative(1, 3).unwrap();
+        assert_eq!(third.to_fixed(2, RoundingMode::HalfEven), Ok(33));
+        assert_eq!(third.to_fixed(2, RoundingMode::Up), Ok(34));
+        assert_eq!((-third).to_fixed(4, RoundingMode::Floor), Ok(-3334));
+        assert_eq!((-third).to_fixed(4, RoundingMode::Ceiling), Ok(-3333));
+        assert_eq!(
+            FractionU32::from(7_u32).to_fixed(3, RoundingMode::Down),
+            Ok(7000)
+        );
+        assert_eq!(
+            FractionU32::from(u32::MAX).to_fixed(10, RoundingMode::Down),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU32::from(1_u32).to_fixed(40, RoundingMode::Down),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU8::with_negative(1, 255)
+                .unwrap()
+                .to_fixed(18, RoundingMode::HalfUp),
+            Ok(-3_921_568_627_450_980)
+        );
+    }
+
+    #[test]
+    fn test_from_fixed() {
+        assert_eq!(
+            FractionU32::from_fixed(33, 2),
+            Ok(FractionU32::with_non_negative(33, 100).unwrap())
+        );
+        assert_eq!(
+            FractionU32::from_fixed(-500, 3),
+            Ok(FractionU32::with_negative(1, 2).unwrap())
+        );
+        assert_eq!(FractionU32::from_fixed(0, 9), Ok(FractionU32::from(0_u32)));
+        assert_eq!(
+            FractionU8::from_fixed(1, 3),
+            Err(RationalError::DenominatorOverflow)
+        );
+        assert_eq!(
+            FractionU8::from_fixed(256, 0),
+            Err(RationalError::NumeratorOverflow)
+        );
+        assert_eq!(
+            FractionU32::from_fixed(1, 39),
+            Err(RationalError::DenominatorOverflow)
+        );
+        // 0不受10^scale溢出的影响
+        assert_eq!(FractionU32::from_fixed(0, 39), Ok(frac(0, 1)));
+        assert_eq!(
+            FractionU8::from_fixed(0, u32::MAX),
+            Ok(FractionU8::from(0_u8))
+        );
+        for v in -2000..=2000 {
+            let f = FractionU32::from_fixed(v, 3).unwrap();
+            for (mode, _) in MIDPOINTS {
+                assert_eq!(f.to_fixed(3, mode), Ok(v));
+            }
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! series提供产生精确有理数的数列迭代器：调和数、Bernoulli数以及任意有理级数的部分和。
+//! 迭代器的元素为`Result`，溢出时产生一个错误，之后不再产生任何元素
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::series::{Harmonic, PartialSums};
+//!
+//! let h: Vec<_> = Harmonic::<u32>::new().take(4).map(Result::unwrap).collect();
+//! assert_eq!(h[3], FractionU32::with_non_negative(25, 12).unwrap());
+//!
+//! // 1/2 + 1/4 + 1/8 + ...
+//! let halves = (1..=3).map(|k| FractionU32::with_non_negative(1, 1 << k).unwrap());
+//! let sums: Vec<_> = PartialSums::new(halves).map(Result::unwrap).collect();
+//! assert_eq!(sums[2], FractionU32::with_non_negative(7, 8).unwrap());
+//! ```
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 1/n，n超出T时返回DenominatorOverflow
+fn reciprocal<T>(n: u64) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+{
+    let one = T::try_from(1).map_err(|_| RationalError::NumeratorOverflow)?;
+    let n = T::try_from(n).map_err(|_| RationalError::DenominatorOverflow)?;
+    Fraction::new(one, n, FractionSign::NonNegative)
+}
+
+/// 调和数迭代器，依次产生H_1 = 1, H_2 = 3/2, H_3 = 11/6, ...
+pub struct Harmonic<T> {
+    n: u64,
+    sum: Option<Fraction<T>>,
+    done: bool,
+}
+
+impl<T> Harmonic<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    pub fn new() -> Self {
+        Self {
+            n: 0,
+            sum: None,
+            done: false,
+        }
+    }
+}
+
+impl<T> Default for Harmonic<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Iterator for Harmonic<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Item = Result<Fraction<T>, RationalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.n += 1;
+        let sum = reciprocal(self.n).and_then(|term| match self.sum {
+            Some(sum) => sum.checked_add(term),
+            None => Ok(term),
+        });
+        match sum {
+            Ok(sum) => self.sum = Some(sum),
+            Err(_) => self.done = true,
+        }
+        Some(sum)
+    }
+}
+
+/// Bernoulli数迭代器，用Akiyama–Tanigawa算法依次产生B_0, B_1, B_2, ...，
+/// 该算法给出的B_1 = +1/2
+#[cfg(feature = "alloc")]
+pub struct Bernoulli<T> {
+    /// Akiyama–Tanigawa表的当前行
+    row: Vec<Fraction<T>>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Bernoulli<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    pub fn new() -> Self {
+        Self {
+            row: Vec::new(),
+            done: false,
+        }
+    }
+
+    /// 表中追加一列1/(m + 1)并自右向左更新A[j - 1] = j(A[j - 1] - A[j])，返回A[0]
+    fn advance(&mut self) -> Result<Fraction<T>, RationalError> {
+        let m = self.row.len() as u64;
+        self.row.push(reciprocal(m + 1)?);
+        for j in (1..self.row.len()).rev() {
+            let k = Fraction::try_from(j as u64)?;
+            self.row[j - 1] = self.row[j - 1].checked_sub(self.row[j])?.checked_mul(k)?;
+        }
+        Ok(self.row[0])
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for Bernoulli<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Iterator for Bernoulli<T>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Item = Result<Fraction<T>, RationalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let b = self.advance();
+        self.done = b.is_err();
+        Some(b)
+    }
+}
+
+/// 部分和迭代器，依次产生a_1, a_1 + a_2, ...
+pub struct PartialSums<I, T> {
+    iter: I,
+    sum: Option<Fraction<T>>,
+    done: bool,
+}
+
+impl<I, T> PartialSums<I, T>
+where
+    I: Iterator<Item = Fraction<T>>,
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    pub fn new(iter: I) -> Self {
+        Self {
+            iter,
+            sum: None,
+            done: false,
+        }
+    }
+}
+
+impl<I, T> Iterator for PartialSums<I, T>
+where
+    I: Iterator<Item = Fraction<T>>,
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    type Item = Result<Fraction<T>, RationalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let term = self.iter.next()?;
+        let sum = match self.sum {
+            Some(sum) => sum.checked_add(term),
+            None => Ok(term),
+        };
+        match sum {
+            Ok(sum) => self.sum = Some(sum),
+            Err(_) => self.done = true,
+        }
+        Some(sum)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        frac::{FractionU32, FractionU8},
+        test_util::frac as f,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_harmonic() {
+        let expected = [f(1, 1), f(3, 2), f(11, 6), f(25, 12), f(137, 60), f(49, 20)];
+        let h: [FractionU32; 6] = core::array::from_fn({
+            let mut it = Harmonic::new();
+            move |_| it.next().unwrap().unwrap()
+        });
+        assert_eq!(h, expected);
+        // H_25超出u32，之后迭代结束
+        let mut it = Harmonic::<u32>::new();
+        assert_eq!(it.by_ref().take_while(Result::is_ok).count(), 24);
+        assert!(it.next().is_none());
+        let mut it = Harmonic::<u8>::new();
+        assert_eq!(it.nth(6), Some(Err(RationalError::NumeratorOverflow)));
+        assert!(it.next().is_none());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_bernoulli() {
+        let expected = [
+            f(1, 1),
+            f(1, 2),
+            f(1, 6),
+            f(0, 1),
+            f(-1, 30),
+            f(0, 1),
+            f(1, 42),
+            f(0, 1),
+            f(-1, 30),
+            f(0, 1),
+            f(5, 66),
+            f(0, 1),
+            f(-691, 2730),
+        ];
+        let b: Vec<_> = Bernoulli::<u32>::new()
+            .take(expected.len())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(b, expected);
+        let mut it = Bernoulli::<u8>::new();
+        assert!(it.by_ref().any(|b| b.is_err()));
+        assert!(it.next().is_none());
+    }
+
+    #[test]
+    fn test_partial_sums() {
+        // 1 - 1/2 + 1/3 - 1/4
+        let terms = (1..=4_i64).map(|k| f(if k % 2 == 0 { -1 } else { 1 }, k as u32));
+        let sums: [FractionU32; 4] = core::array::from_fn({
+            let mut it = PartialSums::new(terms);
+            move |_| it.next().unwrap().unwrap()
+        });
+        assert_eq!(sums, [f(1, 1), f(1, 2), f(5, 6), f(7, 12)]);
+        let mut it = PartialSums::new([200_u8, 50, 10, 1].map(FractionU8::from).into_iter());
+        assert_eq!(it.next(), Some(Ok(FractionU8::from(200_u8))));
+        assert_eq!(it.next(), Some(Ok(FractionU8::from(250_u8))));
+        assert_eq!(it.next(), Some(Err(RationalError::NumeratorOverflow)));
+        assert_eq!(it.next(), None);
+        assert_eq!(
+            PartialSums::new(core::iter::empty::<FractionU32>()).next(),
+            None
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! stats提供分数切片上的精确统计量，中间结果以i128累加，只在最后一步转换回`Fraction<T>`，
+//! 因此和超出T但平均值在范围内时仍能得到结果
+//!
+//! # Example
+//! ```rust
+//! use rat_rs::frac::FractionU32;
+//! use rat_rs::stats;
+//!
+//! let xs = [1_u32, 2, 2, 7].map(FractionU32::from);
+//! assert_eq!(stats::mean(&xs).unwrap(), FractionU32::with_non_negative(3, 1).unwrap());
+//! assert_eq!(stats::median(&xs).unwrap(), FractionU32::from(2_u32));
+//! assert_eq!(stats::mode(&xs).unwrap(), [FractionU32::from(2_u32)]);
+//! assert_eq!(stats::variance(&xs).unwrap(), FractionU32::with_non_negative(11, 2).unwrap());
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::{
+    error::RationalError,
+    frac::{Fraction, UnsignedFractionInt},
+    wide::Wide,
+};
+
+/// 求和
+fn sum<T>(xs: &[Fraction<T>]) -> Result<Wide, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    xs.iter()
+        .try_fold(Wide::ZERO, |acc, x| acc.add(Wide::from_fraction(x)))
+}
+
+/// 平均值
+fn exact_mean<T>(xs: &[Fraction<T>]) -> Result<Wide, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    if xs.is_empty() {
+        return Err(RationalError::EmptyInput);
+    }
+    sum(xs)?.div(Wide::from_len(xs.len()))
+}
+
+/// 离差乘积之和Σ(x - x̄)(y - ȳ)，要求两切片等长且非空
+fn co_moment<T>(xs: &[Fraction<T>], ys: &[Fraction<T>]) -> Result<Wide, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    let (mx, my) = (exact_mean(xs)?, exact_mean(ys)?);
+    xs.iter().zip(ys).try_fold(Wide::ZERO, |acc, (x, y)| {
+        let dx = Wide::from_fraction(x).sub(mx)?;
+        let dy = Wide::from_fraction(y).sub(my)?;
+        acc.add(dx.mul(dy)?)
+    })
+}
+
+/// 算术平均值，输入为空时返回EmptyInput
+pub fn mean<T>(xs: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    exact_mean(xs)?.to_fraction()
+}
+
+/// 加权平均值Σwx/Σw，长度不一致时返回OutOfRange，权重之和为0时返回DivideByZero
+pub fn weighted_mean<T>(
+    xs: &[Fraction<T>],
+    weights: &[Fraction<T>],
+) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    if xs.len() != weights.len() {
+        return Err(RationalError::OutOfRange);
+    }
+    if xs.is_empty() {
+        return Err(RationalError::EmptyInput);
+    }
+    let (total, weight) =
+        xs.iter()
+            .zip(weights)
+            .try_fold((Wide::ZERO, Wide::ZERO), |(total, weight), (x, w)| {
+                let w = Wide::from_fraction(w);
+                Ok::<_, RationalError>((total.add(Wide::from_fraction(x).mul(w)?)?, weight.add(w)?))
+            })?;
+    total.div(weight)?.to_fraction()
+}
+
+/// 中位数，长度为偶数时取中间两数的平均值
+pub fn median<T>(xs: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    if xs.is_empty() {
+        return Err(RationalError::EmptyInput);
+    }
+    let mut sorted = xs.to_vec();
+    sorted.sort_unstable();
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 1 {
+        return Ok(sorted[mid]);
+    }
+    exact_mean(&sorted[mid - 1..=mid])?.to_fraction()
+}
+
+/// 众数，出现次数最多的所有值，升序排列
+pub fn mode<T>(xs: &[Fraction<T>]) -> Result<Vec<Fraction<T>>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt + Eq,
+    u64: From<T>,
+{
+    if xs.is_empty() {
+        return Err(RationalError::EmptyInput);
+    }
+    let mut sorted = xs.to_vec();
+    sorted.sort_unstable();
+    let mut modes = Vec::new();
+    let mut best = 0;
+    for run in sorted.chunk_by(|a, b| a == b) {
+        if run.len() > best {
+            best = run.len();
+            modes.clear();
+        }
+        if run.len() == best {
+            modes.push(run[0]);
+        }
+    }
+    Ok(modes)
+}
+
+/// 总体方差Σ(x - x̄)²/n
+pub fn variance<T>(xs: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    co_moment(xs, xs)?
+        .div(Wide::from_len(xs.len()))?
+        .to_fraction()
+}
+
+/// 样本方差Σ(x - x̄)²/(n - 1)，少于两个值时返回EmptyInput
+pub fn sample_variance<T>(xs: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    if xs.len() < 2 {
+        return Err(RationalError::EmptyInput);
+    }
+    co_moment(xs, xs)?
+        .div(Wide::from_len(xs.len() - 1))?
+        .to_fraction()
+}
+
+/// 总体协方差Σ(x - x̄)(y - ȳ)/n，长度不一致时返回OutOfRange
+pub fn covariance<T>(xs: &[Fraction<T>], ys: &[Fraction<T>]) -> Result<Fraction<T>, RationalError>
+where
+    T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    u64: From<T>,
+{
+    if xs.len() != ys.len() {
+        return Err(RationalError::OutOfRange);
+    }
+    co_moment(xs, ys)?
+        .div(Wide::from_len(xs.len()))?
+        .to_fraction()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{frac::FractionU8, test_util::frac as f};
+
+    use super::*;
+
+    #[test]
+    fn test_mean() {
+        let xs = [f(1, 2), f(1, 3), f(-1, 6)];
+        assert_eq!(mean(&xs), Ok(f(2, 9)));
+        assert_eq!(mean::<u32>(&[]), Err(RationalError::EmptyInput));
+        // 和超出u8，但平均值在范围内
+        let ys = [200_u8, 250, 180].map(FractionU8::from);
+        assert_eq!(mean(&ys), Ok(FractionU8::from(210_u8)));
+        let ws = [f(1, 1), f(2, 1), f(1, 1)];
+        assert_eq!(weighted_mean(&xs, &ws), Ok(f(1, 4)));
+        assert_eq!(
+            weighted_mean(&xs, &[f(1, 1), f(-1, 1), f(0, 1)]),
+            Err(RationalError::DivideByZero)
+        );
+        assert_eq!(weighted_mean(&xs, &ws[..2]), Err(RationalError::OutOfRange));
+    }
+
+    #[test]
+    fn test_median_and_mode() {
+        assert_eq!(median(&[f(3, 1), f(-1, 2), f(1, 3)]), Ok(f(1, 3)));
+        assert_eq!(median(&[f(3, 1), f(-1, 2), f(1, 3), f(5, 1)]), Ok(f(5, 3)));
+        assert_eq!(median(&[f(-7, 2)]), Ok(f(-7, 2)));
+        assert_eq!(median::<u32>(&[]), Err(RationalError::EmptyInput));
+        let xs = [f(1, 2), f(2, 4), f(-3, 1), f(-3, 1), f(7, 1)];
+        assert_eq!(mode(&xs).unwrap(), [f(-3, 1), f(1, 2)]);
+        assert_eq!(mode(&xs[2..]).unwrap(), [f(-3, 1)]);
+        assert_eq!(mode(&[f(2, 1), f(1, 1)]).unwrap(), [f(1, 1), f(2, 1)]);
+    }
+
+    #[test]
+    fn test_variance_and_covariance() {
+        let xs = [f(1, 2), f(3, 2), f(5, 2), f(7, 2)];
+        assert_eq!(variance(&xs), Ok(f(5, 4)));
+        assert_eq!(sample_variance(&xs), Ok(f(5, 3)));
+        assert_eq!(variance(&[f(4, 7)]), Ok(f(0, 1)));
+        assert_eq!(sample_variance(&[f(4, 7)]), Err(RationalError::EmptyInput));
+        let ys = [f(2, 1), f(1, 1), f(0, 1), f(-1, 1)];
+        assert_eq!(covariance(&xs, &ys), Ok(f(-5, 4)));
+        assert_eq!(covariance(&xs, &xs), variance(&xs));
+        assert_eq!(covariance(&xs, &ys[1..]), Err(RationalError::OutOfRange));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let xs = [
+            FractionU8::with_non_negative(1, 255).unwrap(),
+            FractionU8::with_non_negative(1, 254).unwrap(),
+        ];
+        assert_eq!(mean(&xs), Err(RationalError::NumeratorOverflow));
+    }
+}
@@ -0,0 +1,13 @@
+//! 各模块测试共用的辅助函数
+
+use crate::frac::FractionU32;
+
+/// 由带符号的分子与分母构造FractionU32
+pub(crate) fn frac(n: i64, d: u32) -> FractionU32 {
+    let f = FractionU32::with_non_negative(n.unsigned_abs() as u32, d).unwrap();
+    if n < 0 {
+        -f
+    } else {
+        f
+    }
+}
@@ -0,0 +1,182 @@
+//! wide提供crate内部使用的i128有理数，用于中间结果超出T的精确计算
+
+use core::cmp::Ordering;
+
+use crate::{
+    error::RationalError,
+    frac::{gcd_u128, Fraction, FractionSign, UnsignedFractionInt},
+};
+
+/// 带符号的i128拆成绝对值与符号，绝对值超出T时返回NumeratorOverflow
+pub(crate) fn split_signed<T>(n: i128) -> Result<(T, FractionSign), RationalError>
+where
+    T: TryFrom<u64>,
+{
+    let sign = if n < 0 {
+        FractionSign::Negative
+    } else {
+        FractionSign::NonNegative
+    };
+    let numer = u64::try_from(n.unsigned_abs())
+        .ok()
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or(RationalError::NumeratorOverflow)?;
+    Ok((numer, sign))
+}
+
+/// i128有理数，分母恒为正，算术运算的结果与分子互素
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Wide {
+    pub(crate) n: i128,
+    pub(crate) d: i128,
+}
+
+impl Wide {
+    pub(crate) const ZERO: Self = Self { n: 0, d: 1 };
+
+    pub(crate) fn from_fraction<T>(f: &Fraction<T>) -> Self
+    where
+        T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+        u64: From<T>,
+    {
+        Self {
+            n: f.signed_numer(),
+            d: i128::from(u64::from(f.denom())),
+        }
+    }
+
+    /// 约分并把符号移到分子上
+    pub(crate) fn reduced(n: i128, d: i128) -> Self {
+        let g = gcd_u128(n.unsigned_abs(), d.unsigned_abs()) as i128;
+        let (n, d) = if g == 0 { (n, d) } else { (n / g, d / g) };
+        if d < 0 {
+            Self { n: -n, d: -d }
+        } else {
+            Self { n, d }
+        }
+    }
+
+    /// 不约分地转为Fraction，要求分子分母已互素，超出T时返回对应的溢出错误
+    pub(crate) fn to_coprime_fraction<T>(self) -> Result<Fraction<T>, RationalError>
+    where
+        T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    {
+        let (numer, sign) = split_signed(self.n)?;
+        let denom = u64::try_from(self.d)
+            .ok()
+            .and_then(|d| T::try_from(d).ok())
+            .ok_or(RationalError::DenominatorOverflow)?;
+        Ok(Fraction { numer, denom, sign })
+    }
+
+    /// 转为Fraction，分子分母须先能放入T，再由Fraction::new约分
+    pub(crate) fn to_fraction<T>(self) -> Result<Fraction<T>, RationalError>
+    where
+        T: Into<u64> + TryFrom<u64> + UnsignedFractionInt,
+    {
+        let f = self.to_coprime_fraction()?;
+        Fraction::new(f.numer, f.denom, f.sign)
+    }
+
+    pub(crate) fn neg(self) -> Self {
+        Self {
+            n: -self.n,
+            d: self.d,
+        }
+    }
+
+    pub(crate) fn abs(self) -> Self {
+        Self {
+            n: self.n.abs(),
+            d: self.d,
+        }
+    }
+
+    pub(crate) fn add(self, rhs: Self) -> Result<Self, RationalError> {
+        // 以最小公倍数为公分母，延缓中间结果的增长
+        let g = gcd_u128(self.d as u128, rhs.d as u128) as i128;
+        let d = (self.d / g)
+            .checked_mul(rhs.d)
+            .ok_or(RationalError::DenominatorOverflow)?;
+        let n = self
+            .n
+            .checked_mul(rhs.d / g)
+            .and_then(|l| rhs.n.checked_mul(self.d / g)?.checked_add(l))
+            .ok_or(RationalError::NumeratorOverflow)?;
+        Ok(Self::reduced(n, d))
+    }
+
+    pub(crate) fn sub(self, rhs: Self) -> Result<Self, RationalError> {
+        self.add(rhs.neg())
+    }
+
+    /// 先比较符号，同号时按连分数展开逐项比较绝对值，分子分母接近i128上限也不会溢出
+    pub(crate) fn cmp(&self, rhs: &Self) -> Ordering {
+        match (self.n < 0, rhs.n < 0) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => {
+                cmp_ratio(self.n as u128, self.d as u128, rhs.n as u128, rhs.d as u128)
+            }
+            (true, true) => cmp_ratio(
+                rhs.n.unsigned_abs(),
+                rhs.d as u128,
+                self.n.unsigned_abs(),
+                self.d as u128,
+            ),
+        }
+    }
+}
+
+/// 比较a/b与c/d，要求b与d为正数，只做除法与取余
+pub(crate) fn cmp_ratio(mut a: u128, mut b: u128, mut c: u128, mut d: u128) -> Ordering {
+    // 整数部分相同时比较余数r/b与s/d，即反向比较其倒数b/r与d/s
+    let mut flipped = false;
+    loop {
+        let order = match (a / b).cmp(&(c / d)) {
+            Ordering::Equal => match (a % b, c % d) {
+                (0, 0) => Ordering::Equal,
+                (0, _) => Ordering::Less,
+                (_, 0) => Ordering::Greater,
+                (r, s) => {
+                    (a, b, c, d) = (b, r, d, s);
+                    flipped = !flipped;
+                    continue;
+                }
+            },
+            order => order,
+        };
+        return if flipped { order.reverse() } else { order };
+    }
+}
+
+/// 以下仅用于stats
+#[cfg(feature = "alloc")]
+impl Wide {
+    pub(crate) fn from_len(len: usize) -> Self {
+        Self {
+            n: len as i128,
+            d: 1,
+        }
+    }
+
+    pub(crate) fn mul(self, rhs: Self) -> Result<Self, RationalError> {
+        // 先交叉约分，再相乘
+        let a = Self::reduced(self.n, rhs.d);
+        let b = Self::reduced(rhs.n, self.d);
+        let n =
+            a.n.checked_mul(b.n)
+                .ok_or(RationalError::NumeratorOverflow)?;
+        let d =
+            a.d.checked_mul(b.d)
+                .ok_or(RationalError::DenominatorOverflow)?;
+        Ok(Self::reduced(n, d))
+    }
+
+    pub(crate) fn div(self, rhs: Self) -> Result<Self, RationalError> {
+        if rhs.n == 0 {
+            return Err(RationalError::DivideByZero);
+        }
+        self.mul(Self::reduced(rhs.d, rhs.n))
+    }
+}